@@ -17,18 +17,21 @@ use elfo_core::{
     config::AnyConfig,
     errors::RequestError,
     messages::{
-        EntrypointError, Ping, StartEntrypoint, StartEntrypointRejected, UpdateConfig,
-        ValidateConfig,
+        EntrypointError, Ping, StartEntrypoint, StartEntrypointRejected, TopologyChanged,
+        UpdateConfig, ValidateConfig,
     },
     msg, scope,
     signal::{Signal, SignalKind},
     ActorGroup, ActorStatus, Addr, Blueprint, Context, RestartParams, RestartPolicy, Topology,
 };
 
-pub use self::protocol::*;
+pub use self::{protocol::*, sources::ConfigSources};
+
+use self::sources::Provenance;
 
 mod helpers;
 mod protocol;
+mod sources;
 
 // How often warn if a group is updating a config too long.
 const WARN_INTERVAL: Duration = Duration::from_secs(5);
@@ -44,6 +47,13 @@ pub fn from_path(topology: &Topology, path_to_config: impl AsRef<Path>) -> Bluep
     blueprint(topology, source)
 }
 
+/// Loads configs from the stack of sources, see [`ConfigSources`].
+/// Validation errors mention sources of the group's values.
+pub fn from_sources(topology: &Topology, sources: ConfigSources) -> Blueprint {
+    let source = ConfigSource::Layered(sources);
+    blueprint(topology, source)
+}
+
 fn blueprint(topology: &Topology, source: ConfigSource) -> Blueprint {
     let topology = topology.clone();
     ActorGroup::new()
@@ -60,13 +70,28 @@ struct Configurer {
     topology: Topology,
     source: ConfigSource,
     /// Stores hashes of configs per group.
-    versions: FxHashMap<String, u64>,
+    /// Addresses are used as keys because groups can be remounted at runtime.
+    versions: FxHashMap<Addr, u64>,
+    /// The last applied config, sent to groups mounted at runtime.
+    applied: Option<(Value, Option<Provenance>)>,
+}
+
+/// Which groups get configs in `Configurer::update_configs()`.
+#[derive(Clone, Copy)]
+enum UpdateScope {
+    /// Groups whose configs have changed.
+    Changed,
+    /// All groups.
+    All,
+    /// Groups that haven't got any config yet, e.g. mounted at runtime.
+    New,
 }
 
 #[derive(Clone)]
 enum ConfigSource {
     File(PathBuf),
     Fixture(Result<Value, String>),
+    Layered(ConfigSources),
 }
 
 #[derive(Clone)]
@@ -84,6 +109,7 @@ impl Configurer {
             topology,
             source,
             versions: FxHashMap::default(),
+            applied: None,
         }
     }
 
@@ -163,11 +189,17 @@ impl Configurer {
 
                     self.ctx.respond(token, response);
                 }
+                // Configs aren't reloaded, only new groups get the applied one.
+                TopologyChanged => {
+                    let _ = self.update_new_groups().await;
+                }
             })
         }
     }
 
-    async fn load_configs(&self) -> Result<Value, Vec<ReloadConfigsError>> {
+    /// Returns also sources of values if multiple sources are used.
+    async fn load_configs(&self) -> Result<(Value, Option<Provenance>), Vec<ReloadConfigsError>> {
+        let mut provenance = None;
         let config = match &self.source {
             ConfigSource::File(path) => {
                 info!(message = "loading a config", path = %path.to_string_lossy());
@@ -177,6 +209,13 @@ impl Configurer {
                 info!("using a fixture");
                 value.clone()
             }
+            ConfigSource::Layered(sources) => {
+                info!("loading configs from multiple sources");
+                sources.load().await.map(|(value, p)| {
+                    provenance = Some(p);
+                    value
+                })
+            }
         };
 
         let config = match config {
@@ -190,39 +229,76 @@ impl Configurer {
             }
         };
 
-        Deserialize::deserialize(config).map_err(|error| {
+        let config = Deserialize::deserialize(config).map_err(|error| {
             error!(%error, "invalid config");
             vec![ReloadConfigsError {
                 group: scope::meta().group.clone(),
                 reason: error.to_string(),
             }]
-        })
+        })?;
+
+        Ok((config, provenance))
     }
 
     async fn load_and_check_configs(&self) -> Result<(), Vec<ReloadConfigsError>> {
-        let configs = self.load_configs().await?;
+        let (configs, provenance) = self.load_configs().await?;
 
         // Here we rely on the fact that the first `ValidateConfig` message is consumed
         // by the supervisor and no actors are actually started.
         let configs = match_configs(&self.topology, &configs);
-        self.validate_all(&configs).await
+        self.validate_all(&configs, provenance.as_ref()).await
     }
 
     async fn load_and_update_configs(
         &mut self,
         force: bool,
     ) -> Result<(), Vec<ReloadConfigsError>> {
-        let configs = self.load_configs().await?;
+        let (config, provenance) = self.load_configs().await?;
+
+        let scope = if force {
+            UpdateScope::All
+        } else {
+            UpdateScope::Changed
+        };
 
-        let mut configs = match_configs(&self.topology, &configs);
+        self.update_configs(&config, provenance.as_ref(), scope)
+            .await?;
+
+        self.applied = Some((config, provenance));
+        Ok(())
+    }
+
+    async fn update_new_groups(&mut self) -> Result<(), Vec<ReloadConfigsError>> {
+        // Groups mounted before the first config get it on startup.
+        let Some((config, provenance)) = self.applied.take() else {
+            return Ok(());
+        };
+        let result = self
+            .update_configs(&config, provenance.as_ref(), UpdateScope::New)
+            .await;
+        self.applied = Some((config, provenance));
+        result
+    }
+
+    async fn update_configs(
+        &mut self,
+        config: &Value,
+        provenance: Option<&Provenance>,
+        scope: UpdateScope,
+    ) -> Result<(), Vec<ReloadConfigsError>> {
+        let mut configs = match_configs(&self.topology, config);
+
+        // Forget unmounted groups.
+        self.versions
+            .retain(|addr, _| configs.iter().any(|c| c.addr == *addr));
 
         // Filter out up-to-date configs if needed.
-        if !force {
-            configs.retain(|c| {
-                self.versions
-                    .get(&c.group_name)
-                    .map_or(true, |v| c.hash != *v)
-            });
+        match scope {
+            UpdateScope::Changed => {
+                configs.retain(|c| self.versions.get(&c.addr).map_or(true, |v| c.hash != *v))
+            }
+            UpdateScope::All => {}
+            UpdateScope::New => configs.retain(|c| !self.versions.contains_key(&c.addr)),
         }
 
         if configs.is_empty() {
@@ -238,7 +314,7 @@ impl Configurer {
         let status = ActorStatus::NORMAL.with_details("validating");
         self.ctx.set_status(status);
 
-        if let Err(errors) = self.validate_all(&configs).await {
+        if let Err(errors) = self.validate_all(&configs, provenance).await {
             error!("config validation failed");
             self.ctx.set_status(ActorStatus::NORMAL);
             return Err(errors);
@@ -255,7 +331,7 @@ impl Configurer {
         let updated_groups: Vec<String> = configs
             .into_iter()
             .inspect(|config| {
-                self.versions.insert(config.group_name.clone(), config.hash);
+                self.versions.insert(config.addr, config.hash);
             })
             .map(|config| config.group_name)
             .collect();
@@ -271,6 +347,7 @@ impl Configurer {
     async fn validate_all(
         &self,
         configs: &[ConfigWithMeta],
+        provenance: Option<&Provenance>,
     ) -> Result<(), Vec<ReloadConfigsError>> {
         let futures = configs
             .iter()
@@ -301,6 +378,14 @@ impl Configurer {
                 Ok(Ok(_)) | Err(_) => None,
                 Ok(Err(reject)) => Some((group, reject.reason)),
             })
+            .map(|(group, reason)| {
+                let origin = provenance.and_then(|p| p.describe(&group, &reason));
+                let reason = match origin {
+                    Some(origin) => format!("{reason} ({origin})"),
+                    None => reason,
+                };
+                (group, reason)
+            })
             // TODO: include actor keys in the error message.
             .inspect(|(group, reason)| error!(%group, %reason, "invalid config"))
             .map(|(group, reason)| ReloadConfigsError { group, reason })
@@ -370,6 +455,7 @@ async fn ping(ctx: &Context, config_list: &[ConfigWithMeta]) -> bool {
         .filter_map(|result| match result {
             Ok(()) | Err(RequestError::Ignored) => None,
             Err(RequestError::Failed) => Some(String::from("some group is closed")),
+            Err(RequestError::Timeout) => Some(String::from("some group hasn't responded in time")),
         })
         // TODO: include actor keys in the error message.
         .inspect(|reason| error!(%reason, "ping failed"));
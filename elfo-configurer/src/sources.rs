@@ -0,0 +1,451 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde_value::Value;
+use tokio::fs;
+
+use crate::helpers;
+
+/// An ordered stack of config sources, see [`from_sources()`].
+///
+/// Values from later sources override values from earlier ones, sections are
+/// merged deeply, the same way as the `common` section is merged into groups'
+/// ones.
+///
+/// # Example
+/// ```
+/// # use elfo_configurer::ConfigSources;
+/// let sources = ConfigSources::new()
+///     .file("config.toml")
+///     .optional_file("config.production.toml")
+///     .dir("conf.d")
+///     .env("ELFO");
+/// ```
+///
+/// [`from_sources()`]: crate::from_sources
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSources {
+    layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone)]
+enum Layer {
+    File { path: PathBuf, is_required: bool },
+    Dir(PathBuf),
+    Env(String),
+}
+
+impl ConfigSources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a TOML file, which must exist.
+    pub fn file(mut self, path: impl AsRef<Path>) -> Self {
+        self.layers.push(Layer::File {
+            path: path.as_ref().to_path_buf(),
+            is_required: true,
+        });
+        self
+    }
+
+    /// Adds a TOML file, which is skipped if it doesn't exist.
+    /// Useful for environment-specific overrides.
+    pub fn optional_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.layers.push(Layer::File {
+            path: path.as_ref().to_path_buf(),
+            is_required: false,
+        });
+        self
+    }
+
+    /// Adds all `*.toml` files in the directory (like `conf.d`) in
+    /// lexicographical order. Skipped if the directory doesn't exist.
+    pub fn dir(mut self, path: impl AsRef<Path>) -> Self {
+        self.layers.push(Layer::Dir(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Adds environment variables like `<PREFIX>__GROUP__KEY=value`.
+    ///
+    /// Parts separated by `__` are lowercased and form a path, e.g.
+    /// `ELFO__SYSTEM__LOGGERS__FORMAT__WITH_LOCATION=true` sets the
+    /// `format.with_location` parameter of the `system.loggers` group.
+    /// Booleans, numbers, arrays, inline tables and quoted strings are parsed
+    /// as TOML values, others (e.g. dates or `nan`) are kept as strings.
+    pub fn env(mut self, prefix: impl Into<String>) -> Self {
+        self.layers.push(Layer::Env(prefix.into()));
+        self
+    }
+
+    /// Loads and merges all sources.
+    pub(crate) async fn load(&self) -> Result<(Value, Provenance), String> {
+        let mut values = Vec::new();
+
+        for layer in &self.layers {
+            match layer {
+                Layer::File { path, is_required } => {
+                    if let Some(value) = load_file(path, *is_required).await? {
+                        values.push((path.display().to_string(), value));
+                    }
+                }
+                Layer::Dir(path) => {
+                    for path in list_dir(path).await? {
+                        let value = load_file(&path, true).await?.expect("required");
+                        values.push((path.display().to_string(), value));
+                    }
+                }
+                Layer::Env(prefix) => values.extend(env_values(prefix, env::vars())),
+            }
+        }
+
+        Ok(merge(values))
+    }
+}
+
+async fn load_file(path: &Path, is_required: bool) -> Result<Option<Value>, String> {
+    let content = match fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(err) if !is_required && err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("{}: {err}", path.display())),
+    };
+
+    toml::from_str(&content)
+        .map(Some)
+        .map_err(|err| format!("{}: {err}", path.display()))
+}
+
+async fn list_dir(path: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut entries = match fs::read_dir(path).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("{}: {err}", path.display())),
+    };
+
+    let mut paths = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|err| format!("{}: {err}", path.display()))?
+    {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+    Ok(paths)
+}
+
+/// Converts matching environment variables into values, one per variable.
+fn env_values(prefix: &str, vars: impl Iterator<Item = (String, String)>) -> Vec<(String, Value)> {
+    let prefix = format!("{prefix}__");
+
+    let mut vars = vars
+        .filter(|(name, _)| name.starts_with(&prefix))
+        .collect::<Vec<_>>();
+    vars.sort();
+
+    vars.into_iter()
+        .filter_map(|(name, raw)| {
+            let path = name[prefix.len()..].to_lowercase();
+            let mut value = parse_env_value(&raw);
+
+            for part in path.rsplit("__") {
+                if part.is_empty() {
+                    return None;
+                }
+
+                let mut map = BTreeMap::new();
+                map.insert(Value::String(part.into()), value);
+                value = Value::Map(map);
+            }
+
+            Some((format!("env {name}"), value))
+        })
+        .collect()
+}
+
+fn parse_env_value(raw: &str) -> Value {
+    let trimmed = raw.trim_start();
+    let is_typed = |value: &Value| match value {
+        Value::Bool(_) | Value::Seq(_) => true,
+        Value::I64(_) | Value::U64(_) | Value::F64(_) => trimmed
+            .trim_start_matches(['+', '-'])
+            .starts_with(|c: char| c.is_ascii_digit()),
+        Value::String(_) => trimmed.starts_with(['"', '\'']),
+        // Datetimes are maps too, but can't start with `{`.
+        Value::Map(_) => trimmed.starts_with('{'),
+        _ => false,
+    };
+
+    toml::from_str::<Value>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|value| helpers::lookup_value(&value, "value").cloned())
+        .filter(is_typed)
+        .unwrap_or_else(|| Value::String(raw.into()))
+}
+
+/// Merges values in order, later ones override earlier ones.
+fn merge(values: Vec<(String, Value)>) -> (Value, Provenance) {
+    let mut merged = Value::Map(BTreeMap::new());
+    let mut provenance = Provenance::default();
+
+    for (source, value) in values {
+        provenance.record(&source, &value);
+        merged = helpers::add_defaults(Some(value), &merged);
+    }
+
+    (merged, provenance)
+}
+
+/// Tracks which source set each value.
+#[derive(Debug, Default)]
+pub(crate) struct Provenance {
+    sources: Vec<String>,
+    /// A dotted path => an index in `sources`.
+    origins: BTreeMap<String, usize>,
+}
+
+impl Provenance {
+    fn record(&mut self, source: &str, value: &Value) {
+        fn walk(origins: &mut BTreeMap<String, usize>, path: String, value: &Value, no: usize) {
+            match value {
+                Value::Map(map) => {
+                    // The map replaces a value set earlier, if any.
+                    origins.remove(&path);
+
+                    for (key, value) in map {
+                        let Value::String(key) = key else { continue };
+                        let path = if path.is_empty() {
+                            key.clone()
+                        } else {
+                            format!("{path}.{key}")
+                        };
+                        walk(origins, path, value, no);
+                    }
+                }
+                _ => {
+                    // The value replaces a section set earlier, if any.
+                    let prefix = format!("{path}.");
+                    origins.retain(|path, _| !path.starts_with(&prefix));
+                    origins.insert(path, no);
+                }
+            }
+        }
+
+        self.sources.push(source.into());
+        walk(
+            &mut self.origins,
+            String::new(),
+            value,
+            self.sources.len() - 1,
+        );
+    }
+
+    /// Describes sources of the group's values to be added to errors.
+    ///
+    /// Keys mentioned in the reason (e.g. "unknown field `foo`") are preferred,
+    /// otherwise all sources of the group's section are listed. Keys after
+    /// "expected" (e.g. "expected one of `bar`, `baz`") aren't considered,
+    /// because they are expected, not set.
+    pub(crate) fn describe(&self, group: &str, reason: &str) -> Option<String> {
+        let subject = reason.split("expected").next().unwrap_or(reason);
+
+        let is_within = |path: &str, section: &str| {
+            path.strip_prefix(section)
+                .is_some_and(|rest| rest.starts_with('.'))
+        };
+
+        let relevant = self
+            .origins
+            .iter()
+            .filter(|(path, _)| is_within(path, group) || is_within(path, "common"))
+            .collect::<Vec<_>>();
+
+        let mentioned = relevant
+            .iter()
+            .filter(|(path, _)| {
+                let key = path.rsplit('.').next().unwrap_or(path);
+                subject.contains(&format!("`{key}`"))
+            })
+            .map(|(path, no)| format!("`{path}` is set by {}", self.sources[**no]))
+            .collect::<Vec<_>>();
+
+        if !mentioned.is_empty() {
+            return Some(mentioned.join(", "));
+        }
+
+        let mut nos = relevant.iter().map(|(_, no)| **no).collect::<Vec<_>>();
+        nos.sort_unstable();
+        nos.dedup();
+
+        if nos.is_empty() {
+            return None;
+        }
+
+        let sources = nos.iter().map(|no| self.sources[*no].as_str());
+        Some(format!(
+            "values are set by {}",
+            sources.collect::<Vec<_>>().join(", ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toml(s: &str) -> Value {
+        toml::from_str(s).unwrap()
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.into())
+    }
+
+    #[test]
+    fn env_vars() {
+        let vars = [
+            ("ELFO__FOO__A", "42"),
+            ("ELFO__FOO__B_C", "text"),
+            ("ELFO__SYSTEM__LOGGERS__D", "[1, 2]"),
+            ("ELFO__FOO____E", "invalid"),
+            ("ELFO__FOO__F", "2024-01-01"),
+            ("ELFO__FOO__G", "nan"),
+            ("ELFO__FOO__H", "'quoted'"),
+            ("ELFO__FOO__I", "-1.5"),
+            ("OTHER__FOO__A", "1"),
+        ];
+        let vars = vars.iter().map(|(k, v)| (k.to_string(), v.to_string()));
+
+        let (config, _) = merge(env_values("ELFO", vars));
+        assert_eq!(
+            config,
+            toml(
+                r#"
+                foo.a = 42
+                foo.b_c = "text"
+                foo.f = "2024-01-01"
+                foo.g = "nan"
+                foo.h = "quoted"
+                foo.i = -1.5
+                system.loggers.d = [1, 2]
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn layers() {
+        let (config, provenance) = merge(vec![
+            (
+                "base.toml".into(),
+                toml(
+                    r#"
+                    [common]
+                    a = "common"
+                    [foo]
+                    b = "base"
+                    c.d = "base"
+                    c.e = "base"
+                    "#,
+                ),
+            ),
+            ("prod.toml".into(), toml("foo.c.d = 'prod'")),
+            ("env ELFO__FOO__C__E".into(), toml("foo.c.e = 'env'")),
+        ]);
+
+        let lookup = |path| helpers::lookup_value(&config, path).cloned();
+        assert_eq!(lookup("common.a"), Some(string("common")));
+        assert_eq!(lookup("foo.b"), Some(string("base")));
+        assert_eq!(lookup("foo.c.d"), Some(string("prod")));
+        assert_eq!(lookup("foo.c.e"), Some(string("env")));
+
+        assert_eq!(
+            provenance.describe("foo", "invalid type for `d`").unwrap(),
+            "`foo.c.d` is set by prod.toml"
+        );
+        assert_eq!(
+            provenance.describe("foo", "invalid type").unwrap(),
+            "values are set by base.toml, prod.toml, env ELFO__FOO__C__E"
+        );
+        assert_eq!(
+            provenance.describe("bar", "invalid type").unwrap(),
+            "values are set by base.toml"
+        );
+    }
+
+    #[test]
+    fn replaced_values() {
+        let (_, provenance) = merge(vec![
+            ("base.toml".into(), toml("foo.a.b = 1\nfoo.c = 2")),
+            ("prod.toml".into(), toml("foo.a = 3\nfoo.c.d = 4")),
+        ]);
+
+        assert_eq!(
+            provenance.describe("foo", "invalid type").unwrap(),
+            "values are set by prod.toml"
+        );
+    }
+
+    #[test]
+    fn expected_keys() {
+        let (_, provenance) = merge(vec![
+            ("base.toml".into(), toml("foo.a = 1")),
+            ("prod.toml".into(), toml("foo.b = 2")),
+        ]);
+
+        assert_eq!(
+            provenance
+                .describe("foo", "unknown field `b`, expected `a`")
+                .unwrap(),
+            "`foo.b` is set by prod.toml"
+        );
+        assert_eq!(
+            provenance
+                .describe("foo", "unknown field `c`, expected `a` or `b`")
+                .unwrap(),
+            "values are set by base.toml, prod.toml"
+        );
+    }
+
+    #[tokio::test]
+    async fn files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "foo.a = 1").unwrap();
+
+        assert_eq!(load_file(&path, true).await, Ok(Some(toml("foo.a = 1"))));
+
+        let missing = dir.path().join("missing.toml");
+        assert_eq!(load_file(&missing, false).await, Ok(None));
+        let err = load_file(&missing, true).await.unwrap_err();
+        assert!(err.starts_with(&missing.display().to_string()));
+
+        std::fs::write(&path, "foo.a = ").unwrap();
+        let err = load_file(&path, false).await.unwrap_err();
+        assert!(err.starts_with(&path.display().to_string()));
+    }
+
+    #[tokio::test]
+    async fn dirs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        for name in ["20-b.toml", "10-a.toml", "30-c.toml.bak", "README"] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+        std::fs::create_dir(dir.join("nested")).unwrap();
+
+        assert_eq!(
+            list_dir(dir).await.unwrap(),
+            [dir.join("10-a.toml"), dir.join("20-b.toml")]
+        );
+        assert!(list_dir(&dir.join("missing")).await.unwrap().is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt, mem, sync::Arc};
+use std::{fmt, mem, sync::Arc, time::Duration};
 
 use futures_intrusive::sync::ManualResetEvent;
 use metrics::{decrement_gauge, increment_counter, increment_gauge};
@@ -6,12 +6,14 @@ use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use tracing::{error, info, warn};
 
+use elfo_utils::time::Instant;
+
 use crate::{
     envelope::Envelope,
     errors::{SendError, TrySendError},
     group::TerminationPolicy,
-    mailbox::{Mailbox, RecvResult},
-    messages::{ActorStatusReport, Terminate},
+    mailbox::{IdleCheck, Mailbox, MailboxConfig, RecvResult},
+    messages::{ActorStatusReport, Demonitor, Monitor, Terminate},
     msg,
     request_table::RequestTable,
     restarting::RestartPolicy,
@@ -40,6 +42,7 @@ pub struct ActorStatus {
 }
 
 impl ActorStatus {
+    pub(crate) const ABORTED: ActorStatus = ActorStatus::new(ActorStatusKind::Aborted);
     pub const ALARMING: ActorStatus = ActorStatus::new(ActorStatusKind::Alarming);
     pub(crate) const FAILED: ActorStatus = ActorStatus::new(ActorStatusKind::Failed);
     pub const INITIALIZING: ActorStatus = ActorStatus::new(ActorStatusKind::Initializing);
@@ -76,9 +79,9 @@ impl ActorStatus {
         self.kind == ActorStatusKind::Failed
     }
 
-    fn is_finished(&self) -> bool {
+    pub(crate) fn is_finished(&self) -> bool {
         use ActorStatusKind::*;
-        matches!(self.kind, Failed | Terminated)
+        matches!(self.kind, Failed | Terminated | Aborted)
     }
 }
 
@@ -103,6 +106,11 @@ pub enum ActorStatusKind {
     Terminated,
     Alarming,
     Failed,
+    /// The actor hasn't terminated before the deadline and has been aborted,
+    /// see [`TerminationPolicy::deadline()`].
+    ///
+    /// [`TerminationPolicy::deadline()`]: crate::TerminationPolicy::deadline
+    Aborted,
 }
 
 impl ActorStatusKind {
@@ -114,6 +122,7 @@ impl ActorStatusKind {
             ActorStatusKind::Terminated => "Terminated",
             ActorStatusKind::Alarming => "Alarming",
             ActorStatusKind::Failed => "Failed",
+            ActorStatusKind::Aborted => "Aborted",
         }
     }
 }
@@ -127,6 +136,26 @@ pub struct ActorStartInfo {
     /// The cause for the actor start, indicating why the actor is being
     /// initialized.
     pub cause: ActorStartCause,
+    /// The previous run of the actor if it's restarted.
+    /// Useful to degrade gracefully instead of crash-looping, for instance,
+    /// to skip an input that caused a panic.
+    pub previous: Option<PreviousRun>,
+}
+
+/// A struct holding information about the previous run of a restarted actor,
+/// see [`ActorStartInfo::previous`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PreviousRun {
+    /// The final status of the previous run. If the actor failed, details
+    /// contain the panic message or the chain of errors.
+    pub status: ActorStatus,
+    /// The number of the current restart, starting from `1`.
+    /// Restarts are counted until the actor is started by other causes.
+    pub attempt: u64,
+    /// When the previous run finished. Use `finished_at.elapsed()` to get
+    /// how long the actor has been waiting for the restart.
+    pub finished_at: Instant,
 }
 
 /// An enum representing various causes for an actor to start.
@@ -138,25 +167,53 @@ pub enum ActorStartCause {
     /// The actor started in response to a message.
     OnMessage,
     /// The actor started due to the restart policy.
+    /// See [`ActorStartInfo::previous`] for details about the previous run.
     Restarted,
+    /// The actor started in response to a message after being evicted due to
+    /// inactivity, see [`ActorGroup::idle_timeout()`]. Evicted keys are
+    /// remembered only for the idle timeout, later messages start actors
+    /// with [`ActorStartCause::OnMessage`].
+    ///
+    /// [`ActorGroup::idle_timeout()`]: crate::ActorGroup::idle_timeout
+    Evicted,
 }
 
 impl ActorStartInfo {
     pub(crate) fn on_group_mounted() -> Self {
         Self {
             cause: ActorStartCause::GroupMounted,
+            previous: None,
         }
     }
 
     pub(crate) fn on_message() -> Self {
         Self {
             cause: ActorStartCause::OnMessage,
+            previous: None,
         }
     }
 
-    pub(crate) fn on_restart() -> Self {
+    pub(crate) fn on_restart(previous: PreviousRun) -> Self {
         Self {
             cause: ActorStartCause::Restarted,
+            previous: Some(previous),
+        }
+    }
+
+    pub(crate) fn on_evicted() -> Self {
+        Self {
+            cause: ActorStartCause::Evicted,
+            previous: None,
+        }
+    }
+}
+
+impl PreviousRun {
+    pub(crate) fn new(status: ActorStatus, attempt: u64) -> Self {
+        Self {
+            status,
+            attempt,
+            finished_at: Instant::now(),
         }
     }
 }
@@ -173,17 +230,24 @@ impl ActorStartCause {
     pub fn is_on_message(&self) -> bool {
         matches!(self, ActorStartCause::OnMessage)
     }
+
+    pub fn is_evicted(&self) -> bool {
+        matches!(self, ActorStartCause::Evicted)
+    }
 }
 
 // === Actor ===
 
 pub(crate) struct Actor {
     meta: Arc<ActorMeta>,
+    addr: Addr,
     termination_policy: TerminationPolicy,
     mailbox: Mailbox,
     request_table: RequestTable,
     control: RwLock<ControlBlock>,
     finished: ManualResetEvent, // TODO: remove in favor of `status_subscription`?
+    /// Set to abort only this actor, e.g. if it's retired by the router.
+    aborted: ManualResetEvent,
     status_subscription: Arc<SubscriptionManager>,
 }
 
@@ -191,25 +255,31 @@ struct ControlBlock {
     status: ActorStatus,
     /// If `None`, a group's policy will be used.
     restart_policy: Option<RestartPolicy>,
+    /// Actors to be notified when this actor finishes.
+    monitors: Vec<Addr>,
 }
 
 impl Actor {
     pub(crate) fn new(
         meta: Arc<ActorMeta>,
         addr: Addr,
+        mailbox_config: &MailboxConfig,
         termination_policy: TerminationPolicy,
         status_subscription: Arc<SubscriptionManager>,
     ) -> Self {
         Actor {
             meta,
+            addr,
             termination_policy,
-            mailbox: Mailbox::new(),
+            mailbox: Mailbox::new(mailbox_config),
             request_table: RequestTable::new(addr),
             control: RwLock::new(ControlBlock {
                 status: ActorStatus::INITIALIZING,
                 restart_policy: None,
+                monitors: Vec::new(),
             }),
             finished: ManualResetEvent::new(false),
+            aborted: ManualResetEvent::new(false),
             status_subscription,
         }
     }
@@ -223,7 +293,7 @@ impl Actor {
         self.send_status_to_subscribers(&self.control.read());
     }
 
-    pub(crate) fn try_send(&self, envelope: Envelope) -> Result<(), TrySendError<Envelope>> {
+    pub(crate) fn try_send(&self, mut envelope: Envelope) -> Result<(), TrySendError<Envelope>> {
         msg!(match &envelope {
             Terminate { closing } => {
                 if *closing || self.termination_policy.close_mailbox {
@@ -234,24 +304,48 @@ impl Actor {
                     }
                 }
             }
+            Monitor => {
+                if self.add_monitor(envelope.sender()) {
+                    return Ok(());
+                } else {
+                    return Err(TrySendError::Closed(envelope));
+                }
+            }
+            Demonitor => {
+                self.remove_monitor(envelope.sender());
+                return Ok(());
+            }
         });
 
+        envelope.set_recipient(self.addr);
         self.mailbox.try_send(envelope)
     }
 
-    pub(crate) async fn send(&self, envelope: Envelope) -> Result<(), SendError<Envelope>> {
+    pub(crate) async fn send(&self, mut envelope: Envelope) -> Result<(), SendError<Envelope>> {
         msg!(match &envelope {
             Terminate { closing } => {
                 if *closing || self.termination_policy.close_mailbox {
                     if self.close() {
                         return Ok(());
                     } else {
-                        return Err(SendError(envelope));
+                        return Err(SendError::Closed(envelope));
                     }
                 }
             }
+            Monitor => {
+                if self.add_monitor(envelope.sender()) {
+                    return Ok(());
+                } else {
+                    return Err(SendError::Closed(envelope));
+                }
+            }
+            Demonitor => {
+                self.remove_monitor(envelope.sender());
+                return Ok(());
+            }
         });
 
+        envelope.set_recipient(self.addr);
         self.mailbox.send(envelope).await
     }
 
@@ -263,6 +357,36 @@ impl Actor {
         self.mailbox.try_recv()
     }
 
+    pub(crate) fn mark_active(&self) {
+        self.mailbox.mark_active();
+    }
+
+    pub(crate) fn close_if_idle(&self, timeout: Duration) -> IdleCheck {
+        self.mailbox.close_if_idle(timeout, scope::trace_id())
+    }
+
+    /// Closes the evicted mailbox if no envelopes have been sent after eviction.
+    pub(crate) fn try_close_evicted(&self) -> bool {
+        self.mailbox.try_close_evicted()
+    }
+
+    /// Closes the evicted mailbox and returns envelopes sent after eviction.
+    pub(crate) fn close_evicted(&self) -> Vec<Envelope> {
+        self.mailbox.close_evicted()
+    }
+
+    pub(crate) fn mailbox_len(&self) -> usize {
+        self.mailbox.len()
+    }
+
+    pub(crate) fn stash_capacity(&self) -> usize {
+        self.mailbox.stash_capacity()
+    }
+
+    pub(crate) fn set_mailbox_config(&self, config: &MailboxConfig) {
+        self.mailbox.set_config(config);
+    }
+
     pub(crate) fn request_table(&self) -> &RequestTable {
         &self.request_table
     }
@@ -275,6 +399,30 @@ impl Actor {
         self.control.write().restart_policy = policy;
     }
 
+    /// Returns `false` if the actor has already finished.
+    fn add_monitor(&self, watcher: Addr) -> bool {
+        let mut control = self.control.write();
+        if control.status.is_finished() {
+            return false;
+        }
+
+        if !control.monitors.contains(&watcher) {
+            control.monitors.push(watcher);
+        }
+        true
+    }
+
+    fn remove_monitor(&self, watcher: Addr) {
+        self.control.write().monitors.retain(|w| *w != watcher);
+    }
+
+    /// Returns all watchers, must be called after the actor finishes.
+    pub(crate) fn take_monitors(&self) -> Vec<Addr> {
+        let mut control = self.control.write();
+        debug_assert!(control.status.is_finished());
+        mem::take(&mut control.monitors)
+    }
+
     // Note that this method should be called inside a right scope.
     pub(crate) fn set_status(&self, status: ActorStatus) {
         let mut control = self.control.write();
@@ -322,6 +470,10 @@ impl Actor {
         )
     }
 
+    pub(crate) fn is_normal(&self) -> bool {
+        matches!(self.control.read().status.kind, ActorStatusKind::Normal)
+    }
+
     pub(crate) fn is_terminating(&self) -> bool {
         matches!(
             self.control.read().status.kind,
@@ -333,6 +485,14 @@ impl Actor {
         self.finished.wait().await
     }
 
+    pub(crate) fn abort(&self) {
+        self.aborted.set();
+    }
+
+    pub(crate) async fn aborted(&self) {
+        self.aborted.wait().await
+    }
+
     /// Accesses the actor's status under lock to avoid race conditions.
     pub(crate) fn with_status<R>(&self, f: impl FnOnce(ActorStatusReport) -> R) -> R {
         let control = self.control.read();
@@ -353,13 +513,17 @@ impl Actor {
 fn log_status(status: &ActorStatus) {
     if let Some(details) = status.details.as_deref() {
         match status.kind {
-            ActorStatusKind::Failed => error!(status = ?status.kind, %details, "status changed"),
+            ActorStatusKind::Failed | ActorStatusKind::Aborted => {
+                error!(status = ?status.kind, %details, "status changed")
+            }
             ActorStatusKind::Alarming => warn!(status = ?status.kind, %details, "status changed"),
             _ => info!(status = ?status.kind, %details, "status changed"),
         }
     } else {
         match status.kind {
-            ActorStatusKind::Failed => error!(status = ?status.kind, "status changed"),
+            ActorStatusKind::Failed | ActorStatusKind::Aborted => {
+                error!(status = ?status.kind, "status changed")
+            }
             ActorStatusKind::Alarming => warn!(status = ?status.kind, "status changed"),
             _ => info!(status = ?status.kind, "status changed"),
         }
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use idr_ebr::{Guard as EbrGuard, Idr};
+use once_cell::sync::OnceCell;
 
 use crate::{
     addr::{Addr, GroupNo, IdrConfig, NodeLaunchId, NodeNo},
@@ -14,6 +15,8 @@ pub struct AddressBook {
     local: Arc<Idr<Object, IdrConfig>>,
     #[cfg(feature = "network")]
     remote: Arc<RemoteToHandleMap>, // TODO: use `arc_swap::cache::Cache` in TLS?
+    dead_letters: Arc<OnceCell<Addr>>,
+    system_init: Arc<OnceCell<Addr>>,
 }
 
 assert_impl_all!(AddressBook: Sync);
@@ -21,16 +24,44 @@ assert_impl_all!(AddressBook: Sync);
 impl AddressBook {
     pub(crate) fn new(launch_id: NodeLaunchId) -> Self {
         let local = Arc::new(Idr::new());
+        let dead_letters = Default::default();
+        let system_init = Default::default();
 
         #[cfg(feature = "network")]
         return Self {
             launch_id,
             local,
             remote: Default::default(),
+            dead_letters,
+            system_init,
         };
 
         #[cfg(not(feature = "network"))]
-        Self { launch_id, local }
+        Self {
+            launch_id,
+            local,
+            dead_letters,
+            system_init,
+        }
+    }
+
+    /// Returns the address of the group receiving dead letters, if any.
+    pub(crate) fn dead_letters(&self) -> Option<Addr> {
+        self.dead_letters.get().copied()
+    }
+
+    /// Returns `false` if another group has been set already.
+    pub(crate) fn set_dead_letters(&self, addr: Addr) -> bool {
+        self.dead_letters.set(addr).is_ok()
+    }
+
+    /// Returns the address of the actor controlling the node's lifecycle.
+    pub(crate) fn system_init(&self) -> Option<Addr> {
+        self.system_init.get().copied()
+    }
+
+    pub(crate) fn set_system_init(&self, addr: Addr) {
+        let _ = self.system_init.set(addr);
     }
 
     #[cfg(feature = "network")]
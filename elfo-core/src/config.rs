@@ -3,6 +3,7 @@ use std::{
     fmt, mem,
     ops::Deref,
     sync::Arc,
+    time::Duration,
 };
 
 use derive_more::From;
@@ -159,10 +160,14 @@ impl<'de> Deserializer<'de> for AnyConfig {
 #[derive(Debug, Default, Deserialize)]
 #[serde(default)]
 pub(crate) struct SystemConfig {
+    pub(crate) mailbox: crate::mailbox::MailboxConfig,
     pub(crate) logging: crate::logging::LoggingConfig,
     pub(crate) dumping: crate::dumping::DumpingConfig,
     pub(crate) telemetry: crate::telemetry::TelemetryConfig,
     pub(crate) restart_policy: crate::restarting::RestartPolicyConfig,
+    pub(crate) restart_intensity: crate::restarting::RestartIntensityConfig,
+    #[serde(with = "humantime_serde")]
+    pub(crate) idle_timeout: Option<Duration>,
 }
 
 // === Secret ===
@@ -1,8 +1,16 @@
-use std::{future::poll_fn, marker::PhantomData, pin::Pin, sync::Arc, task::Poll};
+use std::{
+    collections::VecDeque,
+    future::{poll_fn, Future},
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::Poll,
+};
 
-use futures::{pin_mut, Stream};
+use futures::{pin_mut, Stream, StreamExt};
 use idr_ebr::Guard as EbrGuard;
 use once_cell::sync::Lazy;
+use tokio::time::{Duration, Instant};
 use tracing::{info, trace};
 
 use elfo_utils::unlikely;
@@ -12,24 +20,26 @@ use crate::{
     addr::Addr,
     address_book::AddressBook,
     config::AnyConfig,
-    coop,
+    coop, dead_letters,
     demux::Demux,
     dumping::{Direction, Dump, Dumper, INTERNAL_CLASS},
     envelope::{AnyMessageBorrowed, AnyMessageOwned, Envelope, EnvelopeOwned, MessageKind},
-    errors::{RequestError, SendError, TryRecvError, TrySendError},
+    errors::{RequestError, SendError, StashError, TryRecvError, TrySendError},
     mailbox::RecvResult,
     message::{Message, Request},
-    messages, msg,
-    object::{Object, OwnedObject},
-    request_table::ResponseToken,
+    messages::{self, ActorDownReason, DeadLetterReason},
+    msg,
+    object::{Object, OwnedObject, Undelivered},
+    request_table::{RequestId, RequestTable, Response, ResponseToken},
     restarting::RestartPolicy,
     routers::Singleton,
     scope,
     source::{SourceHandle, Sources, UnattachedSource},
 };
 
-use self::stats::Stats;
+use self::{stash::Stash, stats::Stats};
 
+mod stash;
 mod stats;
 
 static DUMPER: Lazy<Dumper> = Lazy::new(|| Dumper::new(INTERNAL_CLASS));
@@ -45,6 +55,7 @@ pub struct Context<C = (), K = Singleton> {
     config: Arc<C>,
     key: K,
     sources: Sources,
+    stash: Stash,
     stage: Stage,
     stats: Stats,
 }
@@ -190,6 +201,7 @@ impl<C, K> Context<C, K> {
         let addrs = self.demux.filter(&envelope);
 
         if addrs.is_empty() {
+            self.on_undelivered(DeadLetterReason::Unroutable, Addr::NULL, &envelope);
             return Err(TrySendError::Closed(e2m(envelope)));
         }
 
@@ -197,37 +209,44 @@ impl<C, K> Context<C, K> {
 
         if addrs.len() == 1 {
             return match self.book.get(addrs[0], &guard) {
-                Some(object) => object
-                    .try_send(Addr::NULL, envelope)
-                    .map_err(|err| err.map(e2m)),
-                None => Err(TrySendError::Closed(e2m(envelope))),
+                Some(object) => object.try_deliver(Addr::NULL, envelope).map_err(|err| {
+                    self.on_undelivered(err.reason, Addr::NULL, &err.envelope);
+                    err.into_try_send_error().map(e2m)
+                }),
+                None => {
+                    self.on_undelivered(DeadLetterReason::Unroutable, Addr::NULL, &envelope);
+                    Err(TrySendError::Closed(e2m(envelope)))
+                }
             };
         }
 
         let mut unused = None;
-        let mut has_full = false;
+        let mut reason = None;
         let mut success = false;
 
         for (addr, envelope) in addrs_with_envelope(envelope, &addrs) {
             match self.book.get(addr, &guard) {
-                Some(object) => match object.try_send(Addr::NULL, envelope) {
+                Some(object) => match object.try_deliver(Addr::NULL, envelope) {
                     Ok(()) => success = true,
                     Err(err) => {
-                        has_full |= err.is_full();
-                        forget_and_replace(&mut unused, Some(err.into_inner()));
+                        merge_reason(&mut reason, err.reason);
+                        forget_and_replace(&mut unused, Some(err.envelope));
                     }
                 },
-                None => forget_and_replace(&mut unused, Some(envelope)),
+                None => {
+                    merge_reason(&mut reason, DeadLetterReason::Closed);
+                    forget_and_replace(&mut unused, Some(envelope));
+                }
             };
         }
 
         if success {
             forget_and_replace(&mut unused, None);
             Ok(())
-        } else if has_full {
-            Err(TrySendError::Full(e2m(unused.unwrap())))
         } else {
-            Err(TrySendError::Closed(e2m(unused.unwrap())))
+            let err = Undelivered::new(reason.unwrap(), unused.unwrap());
+            self.on_undelivered(err.reason, Addr::NULL, &err.envelope);
+            Err(err.into_try_send_error().map(e2m))
         }
     }
 
@@ -281,7 +300,8 @@ impl<C, K> Context<C, K> {
         let addrs = self.demux.filter(&envelope);
 
         if addrs.is_empty() {
-            return Err(SendError(e2m(envelope)));
+            self.on_undelivered(DeadLetterReason::Unroutable, Addr::NULL, &envelope);
+            return Err(SendError::Closed(e2m(envelope)));
         }
 
         if addrs.len() == 1 {
@@ -289,14 +309,21 @@ impl<C, K> Context<C, K> {
             return {
                 let guard = EbrGuard::new();
                 let entry = self.book.get(recipient, &guard);
-                let object = ward!(entry, return Err(SendError(e2m(envelope))));
-                Object::send(object, Addr::NULL, envelope)
+                let object = ward!(entry, {
+                    self.on_undelivered(DeadLetterReason::Unroutable, Addr::NULL, &envelope);
+                    return Err(SendError::Closed(e2m(envelope)));
+                });
+                Object::deliver(object, Addr::NULL, envelope)
             }
             .await
-            .map_err(|err| SendError(e2m(err.0)));
+            .map_err(|err| {
+                self.on_undelivered(err.reason, Addr::NULL, &err.envelope);
+                err.into_send_error().map(e2m)
+            });
         }
 
         let mut unused = None;
+        let mut reason = None;
         let mut success = false;
 
         // TODO: send concurrently.
@@ -305,14 +332,18 @@ impl<C, K> Context<C, K> {
                 let guard = EbrGuard::new();
                 let entry = self.book.get(recipient, &guard);
                 let object = ward!(entry, {
+                    merge_reason(&mut reason, DeadLetterReason::Closed);
                     forget_and_replace(&mut unused, Some(envelope));
                     continue;
                 });
-                Object::send(object, Addr::NULL, envelope)
+                Object::deliver(object, Addr::NULL, envelope)
             }
             .await
             .err()
-            .map(|err| err.0);
+            .map(|err| {
+                merge_reason(&mut reason, err.reason);
+                err.envelope
+            });
 
             forget_and_replace(&mut unused, returned_envelope);
             if unused.is_none() {
@@ -324,7 +355,9 @@ impl<C, K> Context<C, K> {
             forget_and_replace(&mut unused, None);
             Ok(())
         } else {
-            Err(SendError(e2m(unused.unwrap())))
+            let err = Undelivered::new(reason.unwrap(), unused.unwrap());
+            self.on_undelivered(err.reason, Addr::NULL, &err.envelope);
+            Err(err.into_send_error().map(e2m))
         }
     }
 
@@ -370,14 +403,20 @@ impl<C, K> Context<C, K> {
         }
 
         {
+            let envelope = Envelope::new(message, kind).upcast();
             let guard = EbrGuard::new();
             let entry = self.book.get(recipient, &guard);
-            let object = ward!(entry, return Err(SendError(message)));
-            let envelope = Envelope::new(message, kind);
-            Object::send(object, recipient, envelope.upcast())
+            let object = ward!(entry, {
+                self.on_undelivered(DeadLetterReason::Unroutable, recipient, &envelope);
+                return Err(SendError::Closed(e2m(envelope)));
+            });
+            Object::deliver(object, recipient, envelope)
         }
         .await
-        .map_err(|err| SendError(e2m(err.0)))
+        .map_err(|err| {
+            self.on_undelivered(err.reason, recipient, &err.envelope);
+            err.into_send_error().map(e2m)
+        })
     }
 
     /// Tries to send a message to the specified recipient.
@@ -413,14 +452,87 @@ impl<C, K> Context<C, K> {
             permit.record(Dump::message(&message, &kind, Direction::Out));
         }
 
+        let envelope = Envelope::new(message, kind).upcast();
         let guard = EbrGuard::new();
         let entry = self.book.get(recipient, &guard);
-        let object = ward!(entry, return Err(TrySendError::Closed(message)));
-        let envelope = Envelope::new(message, kind);
+        let object = ward!(entry, {
+            self.on_undelivered(DeadLetterReason::Unroutable, recipient, &envelope);
+            return Err(TrySendError::Closed(e2m(envelope)));
+        });
 
-        object
-            .try_send(recipient, envelope.upcast())
-            .map_err(|err| err.map(e2m))
+        object.try_deliver(recipient, envelope).map_err(|err| {
+            self.on_undelivered(err.reason, recipient, &err.envelope);
+            err.into_try_send_error().map(e2m)
+        })
+    }
+
+    /// Starts monitoring the specified actor, local or remote.
+    ///
+    /// Once the actor terminates or fails, [`ActorDown`] is sent to this actor.
+    /// If the actor doesn't exist or has already finished, [`ActorDown`] with
+    /// [`ActorDownReason::NotFound`] is sent immediately.
+    ///
+    /// Monitoring the same actor several times produces only one notification.
+    ///
+    /// # Example
+    /// ```ignore
+    /// ctx.monitor(worker).await;
+    ///
+    /// while let Some(envelope) = ctx.recv().await {
+    ///     msg!(match envelope {
+    ///         ActorDown { addr, status, .. } => {
+    ///             info!(%addr, ?status, "worker is down");
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// [`ActorDown`]: messages::ActorDown
+    /// [`ActorDownReason::NotFound`]: messages::ActorDownReason::NotFound
+    pub async fn monitor(&self, addr: Addr) {
+        if self.send_monitoring(addr, messages::Monitor).await {
+            return;
+        }
+
+        let message = messages::ActorDown::new(addr, None, ActorDownReason::NotFound);
+        let kind = MessageKind::Regular { sender: addr };
+        let _ = self.do_send_to(self.actor_addr, message, kind).await;
+    }
+
+    /// Stops monitoring the specified actor, see [`Context::monitor()`].
+    ///
+    /// Note that [`ActorDown`] can be already in the mailbox.
+    ///
+    /// [`ActorDown`]: messages::ActorDown
+    pub async fn demonitor(&self, addr: Addr) {
+        let _ = self.send_monitoring(addr, messages::Demonitor).await;
+    }
+
+    /// Returns `false` if the recipient isn't an existing actor.
+    async fn send_monitoring<M: Message>(&self, addr: Addr, message: M) -> bool {
+        let kind = MessageKind::Regular {
+            sender: self.actor_addr,
+        };
+        let envelope = Envelope::new(message, kind).upcast();
+
+        let fut = {
+            let guard = EbrGuard::new();
+            let object = ward!(self.book.get(addr, &guard), return false);
+
+            // Only actors can be monitored, not groups.
+            if object.is_group() {
+                return false;
+            }
+
+            Object::send(object, addr, envelope)
+        };
+
+        fut.await.is_ok()
+    }
+
+    #[cold]
+    fn on_undelivered(&self, reason: DeadLetterReason, recipient: Addr, envelope: &Envelope) {
+        dead_letters::on_undelivered(&self.book, reason, recipient, envelope);
     }
 
     /// Responds to the requester with the provided response.
@@ -500,6 +612,10 @@ impl<C, K> Context<C, K> {
         'outer: loop {
             self.pre_recv().await;
 
+            if let Some(envelope) = self.pop_unstashed(false) {
+                return Some(envelope);
+            }
+
             let envelope = 'received: {
                 let mailbox_fut = self.actor.as_ref()?.as_actor()?.recv();
                 pin_mut!(mailbox_fut);
@@ -507,8 +623,13 @@ impl<C, K> Context<C, K> {
                 tokio::select! {
                     result = mailbox_fut => match result {
                         RecvResult::Data(envelope) => {
+                            self.stats.on_dequeued_envelope(&envelope);
                             break 'received envelope;
                         },
+                        RecvResult::Stale(envelope) => {
+                            self.stats.on_stale_envelope(&envelope);
+                            continue 'outer;
+                        },
                         RecvResult::Closed(trace_id) => {
                             scope::set_trace_id(trace_id);
                             let actor = self.actor.as_ref()?.as_actor()?;
@@ -518,12 +639,13 @@ impl<C, K> Context<C, K> {
                     },
                     option = self.sources.next(), if !self.sources.is_empty() => {
                         let envelope = ward!(option, continue 'outer);
+                        self.actor.as_ref()?.as_actor()?.mark_active();
                         break 'received envelope;
                     },
                 }
             };
 
-            if let Some(envelope) = self.post_recv(envelope) {
+            if let Some(envelope) = self.post_recv(envelope, false) {
                 return Some(envelope);
             }
         }
@@ -586,9 +708,95 @@ impl<C, K> Context<C, K> {
     where
         C: 'static,
     {
-        #[allow(clippy::never_loop)] // false positive
-        loop {
-            self.pre_recv().await;
+        self.try_recv_impl(false).await
+    }
+
+    /// Receives up to `limit` envelopes into the buffer. If no envelopes are
+    /// available, the method waits for the next one, then takes all available
+    /// envelopes (up to `limit`) without waiting for more.
+    ///
+    /// Returns the number of received envelopes. `0` means that the mailbox is
+    /// closed (or `limit` is zero). If the mailbox is closed while receiving,
+    /// already received envelopes are returned and the next call returns `0`.
+    ///
+    /// Useful to amortize per-message costs, e.g. when writing to a database.
+    /// The handling time is measured for the whole batch, not per envelope.
+    ///
+    /// # Budget
+    ///
+    /// The budget is consumed for every received envelope, so the method can
+    /// return the execution back to the runtime. See [`coop`] for details.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe, envelopes are pushed into the buffer as
+    /// soon as they are received.
+    ///
+    /// # Panics
+    ///
+    /// If the method is called again after `0` is returned for the closed
+    /// mailbox.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use elfo_core as elfo;
+    /// # async fn exec(mut ctx: elfo::Context) {
+    /// # fn handle_batch(_batch: impl Iterator<Item = elfo::Envelope>) {}
+    /// let mut batch = Vec::new();
+    ///
+    /// while ctx.recv_many(&mut batch, 100).await > 0 {
+    ///     handle_batch(batch.drain(..));
+    /// }
+    /// # }
+    /// ```
+    pub async fn recv_many(&mut self, buffer: &mut Vec<Envelope>, limit: usize) -> usize
+    where
+        C: 'static,
+    {
+        if limit == 0 {
+            return 0;
+        }
+
+        buffer.push(ward!(self.recv().await, return 0));
+
+        let mut count = 1;
+        while count < limit {
+            let envelope = ward!(self.try_recv_in_batch().await, break);
+            buffer.push(envelope);
+            count += 1;
+        }
+
+        // The whole batch is handled at once, so it's accounted as a whole.
+        self.stats.on_received_batch();
+        count
+    }
+
+    /// Receives the next envelope of the batch without waiting.
+    async fn try_recv_in_batch(&mut self) -> Option<Envelope>
+    where
+        C: 'static,
+    {
+        self.try_recv_impl(true).await.ok()
+    }
+
+    /// Shared by `try_recv()` and `try_recv_in_batch()`. Inside a batch,
+    /// stats aren't accounted per envelope and the closed mailbox is left to
+    /// be reported by the next call of `recv()`.
+    async fn try_recv_impl(&mut self, in_batch: bool) -> Result<Envelope, TryRecvError>
+    where
+        C: 'static,
+    {
+        'outer: loop {
+            if in_batch {
+                coop::consume_budget().await;
+            } else {
+                self.pre_recv().await;
+            }
+
+            if let Some(envelope) = self.pop_unstashed(in_batch) {
+                return Ok(envelope);
+            }
 
             let envelope = 'received: {
                 let actor = ward!(
@@ -599,8 +807,15 @@ impl<C, K> Context<C, K> {
                 // TODO: poll mailbox and sources fairly.
                 match actor.try_recv() {
                     Some(RecvResult::Data(envelope)) => {
+                        self.stats.on_dequeued_envelope(&envelope);
                         break 'received envelope;
                     }
+                    Some(RecvResult::Stale(envelope)) => {
+                        self.stats.on_stale_envelope(&envelope);
+                        continue 'outer;
+                    }
+                    // The mailbox stays closed, so it's reported by the next call.
+                    Some(RecvResult::Closed(_)) if in_batch => return Err(TryRecvError::Closed),
                     Some(RecvResult::Closed(trace_id)) => {
                         scope::set_trace_id(trace_id);
                         on_input_closed(&mut self.stage, actor);
@@ -617,20 +832,70 @@ impl<C, K> Context<C, K> {
                     .await;
 
                     if let Some(envelope) = envelope {
+                        actor.mark_active();
                         break 'received envelope;
                     }
                 }
 
-                self.stats.on_empty_mailbox();
+                if !in_batch {
+                    self.stats.on_empty_mailbox();
+                }
                 return Err(TryRecvError::Empty);
             };
 
-            if let Some(envelope) = self.post_recv(envelope) {
+            if let Some(envelope) = self.post_recv(envelope, in_batch) {
                 return Ok(envelope);
             }
         }
     }
 
+    /// Defers the envelope until [`Context::unstash_all()`] is called.
+    ///
+    /// Useful to postpone unrelated messages while the actor is in some
+    /// intermediate state, e.g. performing a handshake.
+    ///
+    /// Returns `Err` if the stash is full. The limit is configured by the
+    /// `system.mailbox.stash_capacity` parameter and includes unstashed, but
+    /// not received yet envelopes.
+    ///
+    /// Stashed envelopes are dropped once the actor terminates, so pending
+    /// requests are failed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// while let Some(envelope) = ctx.recv().await {
+    ///     if !is_ready && !envelope.is::<HandshakeDone>() {
+    ///         ctx.stash(envelope)?;
+    ///         continue;
+    ///     }
+    ///
+    ///     msg!(match envelope {
+    ///         HandshakeDone => {
+    ///             is_ready = true;
+    ///             ctx.unstash_all();
+    ///         }
+    ///         SomeRequest => { /* ... */ }
+    ///     });
+    /// }
+    /// ```
+    pub fn stash(&mut self, envelope: Envelope) -> Result<(), StashError<Envelope>> {
+        let actor = ward!(
+            self.actor.as_ref().and_then(|o| o.as_actor()),
+            return Err(StashError(envelope))
+        );
+
+        self.stash
+            .push(envelope, actor.stash_capacity())
+            .map_err(StashError)
+    }
+
+    /// Makes all stashed envelopes available to [`Context::recv()`] and
+    /// [`Context::try_recv()`]. They are received in the original order and
+    /// ahead of any envelopes from the mailbox or sources.
+    pub fn unstash_all(&mut self) {
+        self.stash.unstash_all();
+    }
+
     /// Retrieves information related to the start of the actor.
     ///
     /// # Panics
@@ -654,6 +919,9 @@ impl<C, K> Context<C, K> {
     ///     ActorStartCause::Restarted => {
     ///         // The actor started due to the restart policy.
     ///     }
+    ///     ActorStartCause::Evicted => {
+    ///         // The actor started in response to a message after eviction.
+    ///     }
     ///     _ => {}
     /// }
     /// # }
@@ -682,12 +950,33 @@ impl<C, K> Context<C, K> {
         }
     }
 
-    fn post_recv(&mut self, envelope: Envelope) -> Option<Envelope>
+    fn pop_unstashed(&mut self, in_batch: bool) -> Option<Envelope> {
+        let envelope = self.stash.pop()?;
+
+        // The envelope has been already dumped and handled by `post_recv()`.
+        scope::set_trace_id(envelope.trace_id());
+        if !in_batch {
+            self.stats.on_received_envelope(&envelope);
+        }
+        Some(envelope)
+    }
+
+    /// Envelopes of a batch are accounted by `recv_many()` as a whole.
+    fn post_recv(&mut self, mut envelope: Envelope, in_batch: bool) -> Option<Envelope>
     where
         C: 'static,
     {
         scope::set_trace_id(envelope.trace_id());
 
+        // `ActorDown` received from the network has no address.
+        if let Some(down) = envelope.message().downcast_ref::<messages::ActorDown>() {
+            if down.addr.is_null() {
+                let mut down = down.clone();
+                down.addr = envelope.sender();
+                envelope.set_message(down);
+            }
+        }
+
         let envelope = msg!(match envelope {
             (messages::UpdateConfig { config }, token) => {
                 self.config = config.get_user::<C>().clone();
@@ -716,7 +1005,9 @@ impl<C, K> Context<C, K> {
             self.set_status(ActorStatus::TERMINATING);
         }
 
-        self.stats.on_received_envelope(&envelope);
+        if !in_batch {
+            self.stats.on_received_envelope(&envelope);
+        }
 
         msg!(match envelope {
             (messages::Ping, token) => {
@@ -764,6 +1055,7 @@ impl<C, K> Context<C, K> {
             config: Arc::new(()),
             key: Singleton,
             sources: Sources::new(),
+            stash: Stash::default(),
             stage: self.stage,
             stats: Stats::empty(),
         }
@@ -786,6 +1078,7 @@ impl<C, K> Context<C, K> {
             config,
             key: self.key,
             sources: self.sources,
+            stash: self.stash,
             stage: self.stage,
             stats: self.stats,
         }
@@ -820,6 +1113,7 @@ impl<C, K> Context<C, K> {
             config: self.config,
             key,
             sources: self.sources,
+            stash: self.stash,
             stage: self.stage,
             stats: self.stats,
         }
@@ -870,6 +1164,21 @@ fn forget_and_replace(dest: &mut Option<Envelope>, value: Option<Envelope>) {
     *dest = value;
 }
 
+/// Keeps the most relevant reason if a message hasn't reached several groups:
+/// `Full` over `Unhealthy` over `Closed` over the rest, just like inside a group.
+fn merge_reason(dest: &mut Option<DeadLetterReason>, reason: DeadLetterReason) {
+    let rank = |reason| match reason {
+        DeadLetterReason::Full => 3,
+        DeadLetterReason::Unhealthy => 2,
+        DeadLetterReason::Closed => 1,
+        DeadLetterReason::Unroutable | DeadLetterReason::Discarded => 0,
+    };
+
+    if dest.map_or(true, |prev| rank(prev) < rank(reason)) {
+        *dest = Some(reason);
+    }
+}
+
 impl Context {
     pub(crate) fn new(book: AddressBook, demux: Demux) -> Self {
         Self {
@@ -882,6 +1191,7 @@ impl Context {
             config: Arc::new(()),
             key: Singleton,
             sources: Sources::new(),
+            stash: Stash::default(),
             stage: Stage::PreRecv,
             stats: Stats::empty(),
         }
@@ -901,6 +1211,7 @@ impl<C, K: Clone> Clone for Context<C, K> {
             config: self.config.clone(),
             key: self.key.clone(),
             sources: Sources::new(),
+            stash: Stash::default(),
             stage: self.stage,
             stats: Stats::empty(),
         }
@@ -912,6 +1223,8 @@ pub struct RequestBuilder<'c, C, K, R, M> {
     context: &'c Context<C, K>,
     request: R,
     to: Option<Addr>,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
     marker: PhantomData<M>,
 }
 
@@ -924,6 +1237,8 @@ impl<'c, C, K, R> RequestBuilder<'c, C, K, R, Any> {
             context,
             request,
             to: None,
+            timeout: None,
+            deadline: None,
             marker: PhantomData,
         }
     }
@@ -934,6 +1249,8 @@ impl<'c, C, K, R> RequestBuilder<'c, C, K, R, Any> {
             context: self.context,
             request: self.request,
             to: self.to,
+            timeout: self.timeout,
+            deadline: self.deadline,
             marker: PhantomData,
         }
     }
@@ -946,12 +1263,67 @@ impl<'c, C, K, R, M> RequestBuilder<'c, C, K, R, M> {
         self.to = Some(addr);
         self
     }
+
+    /// Limits the time of resolving the request, including the time of
+    /// sending. The timer starts once `resolve()` is called.
+    ///
+    /// Responses that haven't been received in time are replaced with
+    /// `Err(RequestError::Timeout)`, and late ones are discarded.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let response = ctx
+    ///     .request(SomeCommand)
+    ///     .timeout(Duration::from_secs(5))
+    ///     .resolve()
+    ///     .await?;
+    /// ```
+    #[inline]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Limits the time of resolving the request, including the time of
+    /// sending. If combined with `timeout()`, the earliest time is used.
+    ///
+    /// Responses that haven't been received in time are replaced with
+    /// `Err(RequestError::Timeout)`, and late ones are discarded.
+    ///
+    /// # Stability
+    ///
+    /// This method is unstable, because it accepts [`tokio::time::Instant`],
+    /// which will be replaced in the future to support other runtimes.
+    #[stability::unstable]
+    #[inline]
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    fn effective_deadline(&self) -> Option<Instant> {
+        let by_timeout = self.timeout.map(|timeout| Instant::now() + timeout);
+        match (by_timeout, self.deadline) {
+            (Some(lhs), Some(rhs)) => Some(lhs.min(rhs)),
+            (lhs, rhs) => lhs.or(rhs),
+        }
+    }
+}
+
+/// Returns `None` if the deadline is reached.
+async fn within_deadline<F: Future>(deadline: Option<Instant>, fut: F) -> Option<F::Output> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, fut).await.ok(),
+        None => Some(fut.await),
+    }
 }
 
 // TODO: add `pub async fn id() { ... }`
 impl<'c, C: 'static, K, R: Request> RequestBuilder<'c, C, K, R, Any> {
     /// Waits for the response.
     pub async fn resolve(self) -> Result<R::Response, RequestError> {
+        let deadline = self.effective_deadline();
+
         // TODO: use `context.actor` after removing pruned contexts.
         let this = self.context.actor_addr;
         let object = self.context.book.get_owned(this).expect("invalid addr");
@@ -963,26 +1335,117 @@ impl<'c, C: 'static, K, R: Request> RequestBuilder<'c, C, K, R, Any> {
         let request_id = token.request_id();
         let kind = MessageKind::RequestAny(token);
 
-        let res = if let Some(recipient) = self.to {
-            self.context.do_send_to(recipient, self.request, kind).await
-        } else {
-            self.context.do_send(self.request, kind).await
+        let sending = async {
+            if let Some(recipient) = self.to {
+                self.context.do_send_to(recipient, self.request, kind).await
+            } else {
+                self.context.do_send(self.request, kind).await
+            }
         };
 
-        if res.is_err() {
-            actor.request_table().cancel_request(request_id);
-            return Err(RequestError::Failed);
+        match within_deadline(deadline, sending).await {
+            Some(Ok(())) => {}
+            Some(Err(_)) => {
+                actor.request_table().cancel_request(request_id);
+                return Err(RequestError::Failed);
+            }
+            None => {
+                actor.request_table().cancel_request(request_id);
+                return Err(RequestError::Timeout);
+            }
         }
 
-        let mut responses = actor.request_table().wait(request_id).await;
+        let waiting = actor.request_table().wait(request_id);
+        let mut responses = match within_deadline(deadline, waiting).await {
+            Some(responses) => responses,
+            None => actor.request_table().time_out(request_id),
+        };
         debug_assert_eq!(responses.len(), 1);
-        prepare_response::<R>(responses.pop().expect("missing response"))
+        prepare_response::<R>(responses.pop().expect("missing response").1)
     }
 }
 
 impl<'c, C: 'static, K, R: Request> RequestBuilder<'c, C, K, R, All> {
     /// Waits for the responses.
     pub async fn resolve(self) -> Vec<Result<R::Response, RequestError>> {
+        let deadline = self.effective_deadline();
+        let pending = match self.send_all(deadline).await {
+            Ok(pending) => pending,
+            Err(err) => return vec![Err(err)],
+        };
+
+        let table = pending.table();
+        let responses = match within_deadline(deadline, table.wait(pending.request_id)).await {
+            Some(responses) => responses,
+            None => table.time_out(pending.request_id),
+        };
+
+        (responses.into_iter())
+            .map(|(_, response)| prepare_response::<R>(response))
+            .collect()
+    }
+
+    /// Returns a stream of responses in order of receiving, so slow
+    /// recipients don't delay already received responses.
+    ///
+    /// Every item is paired with the responder's address. It's [`Addr::NULL`]
+    /// only if the responder is unknown, e.g. the request hasn't been sent or
+    /// a remote recipient hasn't responded in time.
+    ///
+    /// Dropping the stream cancels the request, late responses are discarded.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let stream = ctx.request(SomeQuery).all().resolve_stream();
+    /// pin_mut!(stream);
+    ///
+    /// while let Some((addr, response)) = stream.next().await {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn resolve_stream(
+        self,
+    ) -> impl Stream<Item = (Addr, Result<R::Response, RequestError>)> + 'c {
+        let deadline = self.effective_deadline();
+
+        futures::stream::once(self.send_all(deadline))
+            .flat_map(|pending| {
+                futures::stream::unfold(Some(pending), |pending| async move {
+                    match pending? {
+                        Ok(mut pending) => {
+                            let response = pending.next().await?;
+                            Some((response, Some(Ok(pending))))
+                        }
+                        Err(err) => Some(((Addr::NULL, Err(err)), None)),
+                    }
+                })
+            })
+            .map(|(responder, response)| (responder, prepare_response::<R>(response)))
+    }
+
+    /// Waits for the first `quorum` successful responses and cancels the
+    /// request, late responses are discarded. Errors are skipped.
+    ///
+    /// Returns fewer than `quorum` responses only if all recipients have
+    /// responded or the deadline is reached.
+    pub async fn resolve_quorum(self, quorum: usize) -> Vec<(Addr, R::Response)> {
+        let deadline = self.effective_deadline();
+        let mut pending = ward!(self.send_all(deadline).await.ok(), return Vec::new());
+        let mut responses = Vec::with_capacity(quorum);
+
+        while responses.len() < quorum {
+            let (responder, response) = ward!(pending.next().await, break);
+
+            if let Ok(response) = prepare_response::<R>(response) {
+                responses.push((responder, response));
+            }
+        }
+
+        // Remaining responses are discarded on dropping `pending`.
+        responses
+    }
+
+    async fn send_all(self, deadline: Option<Instant>) -> Result<PendingRequest, RequestError> {
         // TODO: use `context.actor` after removing pruned contexts.
         let this = self.context.actor_addr;
         let object = self.context.book.get_owned(this).expect("invalid addr");
@@ -994,24 +1457,73 @@ impl<'c, C: 'static, K, R: Request> RequestBuilder<'c, C, K, R, All> {
         let request_id = token.request_id();
         let kind = MessageKind::RequestAll(token);
 
-        let res = if let Some(recipient) = self.to {
-            self.context.do_send_to(recipient, self.request, kind).await
-        } else {
-            self.context.do_send(self.request, kind).await
+        // Cancels the request on failures.
+        let pending = PendingRequest {
+            object,
+            request_id,
+            deadline,
+            received: VecDeque::new(),
+            is_done: false,
         };
 
-        if res.is_err() {
-            actor.request_table().cancel_request(request_id);
-            return vec![Err(RequestError::Failed)];
+        let sending = async {
+            if let Some(recipient) = self.to {
+                self.context.do_send_to(recipient, self.request, kind).await
+            } else {
+                self.context.do_send(self.request, kind).await
+            }
+        };
+
+        match within_deadline(deadline, sending).await {
+            Some(Ok(())) => Ok(pending),
+            Some(Err(_)) => Err(RequestError::Failed),
+            None => Err(RequestError::Timeout),
+        }
+    }
+}
+
+/// A sent `All` request, which is cancelled on drop.
+struct PendingRequest {
+    object: OwnedObject,
+    request_id: RequestId,
+    deadline: Option<Instant>,
+    /// Received, but not consumed yet responses.
+    received: VecDeque<Response>,
+    /// Set once all responses are received or the deadline is reached.
+    is_done: bool,
+}
+
+impl PendingRequest {
+    fn table(&self) -> &RequestTable {
+        let actor = self
+            .object
+            .as_actor()
+            .expect("can be called only on actors");
+        actor.request_table()
+    }
+
+    async fn next(&mut self) -> Option<Response> {
+        if self.received.is_empty() && !self.is_done {
+            let waiting = self.table().wait_next(self.request_id);
+            match within_deadline(self.deadline, waiting).await {
+                Some(Some(responses)) => self.received.extend(responses),
+                Some(None) => self.is_done = true,
+                None => {
+                    let responses = self.table().time_out(self.request_id);
+                    self.received.extend(responses);
+                    self.is_done = true;
+                }
+            }
         }
 
-        actor
-            .request_table()
-            .wait(request_id)
-            .await
-            .into_iter()
-            .map(prepare_response::<R>)
-            .collect()
+        self.received.pop_front()
+    }
+}
+
+impl Drop for PendingRequest {
+    fn drop(&mut self) {
+        // Does nothing if the request has been completed.
+        self.table().cancel_request(self.request_id);
     }
 }
 
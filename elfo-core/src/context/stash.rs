@@ -0,0 +1,69 @@
+use std::{collections::VecDeque, mem};
+
+use metrics::{decrement_gauge, increment_gauge};
+use parking_lot::Mutex;
+use static_assertions::assert_impl_all;
+
+use crate::envelope::Envelope;
+
+/// Envelopes deferred by the actor, see `Context::stash()`.
+///
+/// Envelopes aren't `Sync`, but `Context` must be, so queues are wrapped into
+/// a mutex. It's never contended, because it's accessed only by `&mut self`.
+#[derive(Default)]
+pub(super) struct Stash(Mutex<Queues>);
+
+assert_impl_all!(Stash: Sync);
+
+#[derive(Default)]
+struct Queues {
+    stashed: VecDeque<Envelope>,
+    /// Envelopes to be received ahead of the mailbox.
+    unstashed: VecDeque<Envelope>,
+}
+
+impl Stash {
+    /// Returns the envelope back if the stash already contains `capacity`
+    /// envelopes, including unstashed but not received yet.
+    pub(super) fn push(&mut self, envelope: Envelope, capacity: usize) -> Result<(), Envelope> {
+        let queues = self.0.get_mut();
+        if queues.len() >= capacity {
+            return Err(envelope);
+        }
+
+        queues.stashed.push_back(envelope);
+        increment_gauge!("elfo_stashed_envelopes", 1.);
+        Ok(())
+    }
+
+    pub(super) fn unstash_all(&mut self) {
+        let queues = self.0.get_mut();
+        if queues.unstashed.is_empty() {
+            mem::swap(&mut queues.stashed, &mut queues.unstashed);
+        } else {
+            // Previously unstashed envelopes are still ahead.
+            queues.unstashed.append(&mut queues.stashed);
+        }
+    }
+
+    pub(super) fn pop(&mut self) -> Option<Envelope> {
+        let envelope = self.0.get_mut().unstashed.pop_front()?;
+        decrement_gauge!("elfo_stashed_envelopes", 1.);
+        Some(envelope)
+    }
+}
+
+impl Queues {
+    fn len(&self) -> usize {
+        self.stashed.len() + self.unstashed.len()
+    }
+}
+
+impl Drop for Stash {
+    fn drop(&mut self) {
+        let len = self.0.get_mut().len();
+        if len > 0 {
+            decrement_gauge!("elfo_stashed_envelopes", len as f64);
+        }
+    }
+}
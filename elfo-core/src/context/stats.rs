@@ -17,6 +17,7 @@ struct InHandling {
 
 static STARTUP_LABELS: &[Label] = &[Label::from_static_parts("message", "<Startup>")];
 static EMPTY_MAILBOX_LABELS: &[Label] = &[Label::from_static_parts("message", "<EmptyMailbox>")];
+static BATCH_LABELS: &[Label] = &[Label::from_static_parts("message", "<Batch>")];
 
 impl Stats {
     pub(super) fn empty() -> Self {
@@ -34,18 +35,27 @@ impl Stats {
         self.emit_handling_time();
     }
 
+    /// Called only for envelopes received from the mailbox, not from sources.
+    pub(super) fn on_dequeued_envelope(&self, envelope: &Envelope) {
+        let recorder = ward!(metrics::try_recorder());
+        let key = Key::from_static_parts(
+            "elfo_message_waiting_time_seconds",
+            envelope.message().labels(),
+        );
+        let value = Instant::now().secs_f64_since(envelope.enqueued_time());
+        recorder.record_histogram(&key, value);
+    }
+
     pub(super) fn on_received_envelope(&mut self, envelope: &Envelope) {
         debug_assert!(self.in_handling.is_none());
 
-        let recorder = ward!(metrics::try_recorder());
-        let key = Key::from_static_name("elfo_message_waiting_time_seconds");
-        let now = Instant::now();
-
-        // Now envelope cannot be forwarded, so use the created time as a start time.
-        let value = now.secs_f64_since(envelope.created_time());
-        recorder.record_histogram(&key, value);
+        let labels = envelope.message().labels();
+        self.in_handling = Some(InHandling::new(labels, Instant::now()));
+    }
 
-        self.in_handling = Some(InHandling::new(envelope.message().labels(), now));
+    /// Called once the rest of the batch is received after the first envelope.
+    pub(super) fn on_received_batch(&mut self) {
+        self.in_handling = Some(InHandling::new(BATCH_LABELS, Instant::now()));
     }
 
     pub(super) fn on_empty_mailbox(&mut self) {
@@ -54,6 +64,12 @@ impl Stats {
         self.in_handling = Some(InHandling::new(EMPTY_MAILBOX_LABELS, Instant::now()));
     }
 
+    pub(super) fn on_stale_envelope(&self, envelope: &Envelope) {
+        let recorder = ward!(metrics::try_recorder());
+        let key = Key::from_static_parts("elfo_stale_messages_total", envelope.message().labels());
+        recorder.increment_counter(&key, 1);
+    }
+
     pub(super) fn on_sent_message(&self, message: &impl Message) {
         let recorder = ward!(metrics::try_recorder());
         let key = Key::from_static_parts("elfo_sent_messages_total", message.labels());
@@ -74,3 +90,132 @@ impl Drop for Stats {
         self.emit_handling_time();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Mutex, Once},
+        time::Duration,
+    };
+
+    use metrics::{GaugeValue, Recorder, Unit};
+
+    use elfo_utils::time::with_instant_mock;
+
+    use super::*;
+    use crate::{
+        envelope::MessageKind,
+        mailbox::{Mailbox, MailboxConfig, RecvResult},
+        message,
+        tracing::TraceId,
+        Addr,
+    };
+
+    /// Histograms as `(name, message, value)`.
+    static HISTOGRAMS: Mutex<Vec<(String, String, f64)>> = Mutex::new(Vec::new());
+
+    struct Capture;
+
+    impl Recorder for Capture {
+        fn register_counter(&self, _: &Key, _: Option<Unit>, _: Option<&'static str>) {}
+        fn register_gauge(&self, _: &Key, _: Option<Unit>, _: Option<&'static str>) {}
+        fn register_histogram(&self, _: &Key, _: Option<Unit>, _: Option<&'static str>) {}
+        fn increment_counter(&self, _: &Key, _: u64) {}
+        fn update_gauge(&self, _: &Key, _: GaugeValue) {}
+
+        fn record_histogram(&self, key: &Key, value: f64) {
+            let message = key
+                .labels()
+                .find(|label| label.key() == "message")
+                .map_or("", |label| label.value());
+
+            let item = (key.name().to_string(), message.to_string(), value);
+            HISTOGRAMS.lock().unwrap().push(item);
+        }
+    }
+
+    fn capture() {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| metrics::set_boxed_recorder(Box::new(Capture)).unwrap());
+    }
+
+    fn histograms(name: &str, message: &str) -> Vec<f64> {
+        HISTOGRAMS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(n, m, _)| n == name && m == message)
+            .map(|(_, _, value)| *value)
+            .collect()
+    }
+
+    fn envelope(message: impl Message) -> Envelope {
+        let kind = MessageKind::Regular { sender: Addr::NULL };
+        Envelope::with_trace_id(message, kind, TraceId::try_from(1).unwrap()).upcast()
+    }
+
+    fn assert_approx_eq(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn waiting_time_from_enqueue() {
+        #[message]
+        struct WaitingTimeFromEnqueue;
+
+        capture();
+        with_instant_mock(|mock| {
+            let mailbox = Mailbox::new(&MailboxConfig::default());
+            let envelope = envelope(WaitingTimeFromEnqueue);
+
+            // The time before enqueueing isn't counted.
+            mock.advance(Duration::from_secs(5));
+            mailbox.try_send(envelope).unwrap();
+            mock.advance(Duration::from_secs(2));
+
+            let Some(RecvResult::Data(envelope)) = mailbox.try_recv() else {
+                panic!("no envelope");
+            };
+            Stats::empty().on_dequeued_envelope(&envelope);
+        });
+
+        let waiting_time = histograms(
+            "elfo_message_waiting_time_seconds",
+            "WaitingTimeFromEnqueue",
+        );
+        assert_approx_eq(&waiting_time, &[2.]);
+    }
+
+    #[test]
+    fn waiting_time_by_message() {
+        #[message]
+        struct WaitingTimeFirst;
+
+        #[message]
+        struct WaitingTimeSecond;
+
+        capture();
+        with_instant_mock(|mock| {
+            let mailbox = Mailbox::new(&MailboxConfig::default());
+            mailbox.try_send(envelope(WaitingTimeFirst)).unwrap();
+            mock.advance(Duration::from_secs(1));
+            mailbox.try_send(envelope(WaitingTimeSecond)).unwrap();
+            mock.advance(Duration::from_secs(1));
+            mailbox.try_send(envelope(WaitingTimeFirst)).unwrap();
+            mock.advance(Duration::from_secs(1));
+
+            let stats = Stats::empty();
+            while let Some(RecvResult::Data(envelope)) = mailbox.try_recv() {
+                stats.on_dequeued_envelope(&envelope);
+            }
+        });
+
+        let first = histograms("elfo_message_waiting_time_seconds", "WaitingTimeFirst");
+        assert_approx_eq(&first, &[3., 1.]);
+        let second = histograms("elfo_message_waiting_time_seconds", "WaitingTimeSecond");
+        assert_approx_eq(&second, &[2.]);
+    }
+}
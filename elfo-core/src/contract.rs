@@ -0,0 +1,126 @@
+use std::any::TypeId;
+
+use tracing::warn;
+
+use crate::{
+    addr::Addr,
+    errors::{StartError, StartGroupError},
+    message::{self, AnyMessage, Message},
+    topology::{ConnectionTo, Topology},
+};
+
+/// Messages declared by `ActorGroup::accepts()` and `ActorGroup::emits()`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Contract {
+    accepts: Vec<MessageName>,
+    emits: Vec<MessageName>,
+}
+
+/// `(protocol, name)`, unique across the node, see `check_messages_uniqueness`.
+type MessageName = (&'static str, &'static str);
+
+impl Contract {
+    #[track_caller]
+    pub(crate) fn add_accepted<M: Message>(&mut self) {
+        self.accepts.push(message_name::<M>());
+    }
+
+    #[track_caller]
+    pub(crate) fn add_emitted<M: Message>(&mut self) {
+        self.emits.push(message_name::<M>());
+    }
+
+    /// Groups without `accepts()` are considered to accept any message.
+    fn may_accept(&self, name: &MessageName) -> bool {
+        self.accepts.is_empty() || self.accepts.contains(name)
+    }
+
+    /// Returns `true` if both contracts are declared, but the destination
+    /// accepts none of messages emitted by the source.
+    fn is_dead_route_to(&self, dest: &Contract) -> bool {
+        !dest.accepts.is_empty() && !self.emits.iter().any(|name| dest.may_accept(name))
+    }
+}
+
+/// Resolves the name in the message registry, see `lookup_vtable_by_type`.
+/// Falls back to the type's path if the message cannot be found unambiguously.
+#[track_caller]
+fn message_name<M: Message>() -> MessageName {
+    assert!(
+        TypeId::of::<M>() != TypeId::of::<AnyMessage>(),
+        "only concrete messages are allowed"
+    );
+
+    if let Some(vtable) = message::lookup_vtable_by_type::<M>() {
+        return (vtable.protocol, vtable.name);
+    }
+
+    let path = std::any::type_name::<M>();
+    let name = path.rsplit("::").next().unwrap_or(path);
+    let krate = path.split("::").next().unwrap_or(path);
+    (krate, name)
+}
+
+/// Checks that every emitted message has at least one route to a group
+/// accepting it.
+///
+/// Filters of routes can't be inspected and remote groups have no contracts,
+/// so any route to a remote group is assumed to carry every emitted message.
+/// Thus, groups having such routes are never reported.
+///
+/// Also warns about dead routes, i.e. routes between groups with contracts,
+/// which don't carry any emitted message.
+pub(crate) fn check(topology: &Topology) -> Result<(), StartError> {
+    let locals = topology.locals().collect::<Vec<_>>();
+    let find = |addr: Addr| locals.iter().find(|group| group.addr == addr);
+    let mut errors = Vec::new();
+
+    for source in &locals {
+        if source.contract.emits.is_empty() {
+            continue;
+        }
+
+        let conns = topology
+            .connections()
+            .filter(|conn| conn.from == source.addr)
+            .collect::<Vec<_>>();
+
+        let has_remote = conns
+            .iter()
+            .any(|conn| conn.to.clone().into_remote().is_some());
+        let dests = conns
+            .iter()
+            .filter_map(|conn| match conn.to {
+                ConnectionTo::Local(addr) => find(addr),
+                #[cfg(feature = "network")]
+                ConnectionTo::Remote(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        for name in &source.contract.emits {
+            if !has_remote && !dests.iter().any(|dest| dest.contract.may_accept(name)) {
+                let (protocol, message) = name;
+                errors.push(StartGroupError {
+                    group: source.name.clone(),
+                    reason: format!("no route for emitted `{protocol}/{message}`"),
+                });
+            }
+        }
+
+        for dest in dests {
+            if source.contract.is_dead_route_to(&dest.contract) {
+                warn!(
+                    from = %source.name,
+                    to = %dest.name,
+                    "dead route, no emitted messages are accepted by the destination"
+                );
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(StartError::multiple(errors))
+    }
+}
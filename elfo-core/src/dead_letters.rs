@@ -0,0 +1,50 @@
+use idr_ebr::Guard as EbrGuard;
+use metrics::increment_counter;
+use once_cell::sync::Lazy;
+
+use crate::{
+    address_book::AddressBook,
+    dumping::{Direction, Dump, Dumper},
+    envelope::{Envelope, MessageKind},
+    messages::{DeadLetter, DeadLetterReason},
+    Addr,
+};
+
+static DUMPER: Lazy<Dumper> = Lazy::new(|| Dumper::new("dead_letters"));
+
+/// Called when the envelope hasn't reached any mailbox.
+/// The envelope itself is returned to the sender, so only a copy is forwarded.
+/// It's always counted and dumped, but forwarded only if a dead letters group
+/// is configured in the topology.
+pub(crate) fn on_undelivered(
+    book: &AddressBook,
+    reason: DeadLetterReason,
+    recipient: Addr,
+    envelope: &Envelope,
+) {
+    increment_counter!("elfo_dead_letters_total", "reason" => reason.as_str());
+
+    if let Some(permit) = DUMPER.acquire_m(envelope.message()) {
+        permit.record(Dump::message(
+            envelope.message(),
+            envelope.message_kind(),
+            Direction::Out,
+        ));
+    }
+
+    let dead_letters = ward!(book.dead_letters());
+    let message = DeadLetter {
+        reason,
+        sender: envelope.sender().into(),
+        recipient: recipient.into(),
+        message: envelope.message().clone(),
+    };
+
+    let kind = MessageKind::Regular { sender: Addr::NULL };
+    let envelope = Envelope::with_trace_id(message, kind, envelope.trace_id()).upcast();
+
+    // Never wait here and never produce dead letters for dead letters.
+    let guard = EbrGuard::new();
+    let object = ward!(book.get(dead_letters, &guard));
+    let _ = object.try_send(Addr::NULL, envelope);
+}
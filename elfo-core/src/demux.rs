@@ -1,5 +1,7 @@
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 
+use arc_swap::ArcSwapOption;
+use parking_lot::Mutex;
 use smallvec::SmallVec;
 
 use crate::{envelope::Envelope, Addr};
@@ -7,31 +9,120 @@ use crate::{envelope::Envelope, Addr};
 const OPTIMAL_COUNT: usize = 5;
 type Addrs = SmallVec<[Addr; OPTIMAL_COUNT]>;
 
+type Filter = Arc<dyn Fn(&Envelope, &mut Addrs) + Send + Sync>;
+
+#[derive(Clone)]
+struct Route {
+    /// A group the route leads to, `Addr::NULL` for remote groups.
+    dest: Addr,
+    filter: Filter,
+}
+
 // Actually, it's a private type, `pub` is for `Destination` only.
+//
+// Clones share the same routes, thus routes appended at runtime (e.g. to a
+// group mounted after the start) are visible to all contexts of the group.
 #[derive(Default, Clone)]
 pub struct Demux {
-    #[allow(clippy::type_complexity)]
-    filter: Option<Arc<dyn Fn(&Envelope, &mut Addrs) + Send + Sync>>,
+    shared: Arc<Shared>,
+}
+
+#[derive(Default)]
+struct Shared {
+    routes: Mutex<Vec<Route>>,
+    /// All routes composed into one filter, rebuilt once routes are changed.
+    filter: ArcSwapOption<Filter>,
 }
 
 impl Demux {
-    pub(crate) fn append(&mut self, f: impl Fn(&Envelope, &mut Addrs) + Send + Sync + 'static) {
-        self.filter = Some(if let Some(prev) = self.filter.take() {
-            Arc::new(move |envelope, addrs| {
-                prev(envelope, addrs);
-                f(envelope, addrs);
-            })
-        } else {
-            Arc::new(f)
-        })
+    pub(crate) fn append(
+        &self,
+        dest: Addr,
+        f: impl Fn(&Envelope, &mut Addrs) + Send + Sync + 'static,
+    ) {
+        let mut routes = self.shared.routes.lock();
+        routes.push(Route {
+            dest,
+            filter: Arc::new(f),
+        });
+        self.shared.filter.store(compose(&routes).map(Arc::new));
+    }
+
+    /// Removes all routes to the group, e.g. once it's unmounted.
+    pub(crate) fn remove(&self, dest: Addr) {
+        let mut routes = self.shared.routes.lock();
+        routes.retain(|route| route.dest != dest);
+        self.shared.filter.store(compose(&routes).map(Arc::new));
     }
 
     // TODO: return an iterator?
     pub(crate) fn filter(&self, envelope: &Envelope) -> Addrs {
         let mut addrs = Addrs::new();
-        if let Some(filter) = &self.filter {
+        if let Some(filter) = &*self.shared.filter.load() {
             (filter)(envelope, &mut addrs);
         }
         addrs
     }
 }
+
+fn compose(routes: &[Route]) -> Option<Filter> {
+    routes.iter().fold(None, |prev, route| {
+        let f = route.filter.clone();
+        Some(if let Some(prev) = prev {
+            Arc::new(move |envelope: &Envelope, addrs: &mut Addrs| {
+                prev(envelope, addrs);
+                f(envelope, addrs);
+            })
+        } else {
+            f
+        })
+    })
+}
+
+impl fmt::Debug for Demux {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Demux").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use idr_ebr::Key;
+
+    use crate::{
+        addr::{GroupNo, NodeLaunchId},
+        envelope::MessageKind,
+        message,
+        tracing::TraceId,
+    };
+
+    #[message]
+    struct Test;
+
+    #[test]
+    fn remove() {
+        let envelope = Envelope::with_trace_id(
+            Test,
+            MessageKind::Regular { sender: Addr::NULL },
+            TraceId::try_from(1).unwrap(),
+        )
+        .upcast();
+
+        let demux = Demux::default();
+        let clone = demux.clone();
+        let launch_id = NodeLaunchId::from_bits(1);
+        let addr = |no| {
+            let group_no = GroupNo::new(no, launch_id).unwrap();
+            Addr::new_local(Key::try_from(1).unwrap(), group_no, launch_id)
+        };
+        let (a, b) = (addr(1), addr(2));
+        demux.append(a, move |_, addrs| addrs.push(a));
+        demux.append(b, move |_, addrs| addrs.push(b));
+        assert_eq!(&clone.filter(&envelope)[..], [a, b]);
+
+        demux.remove(a);
+        assert_eq!(&clone.filter(&envelope)[..], [b]);
+    }
+}
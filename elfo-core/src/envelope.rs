@@ -10,7 +10,8 @@ use crate::{
 // TODO: use granular messages instead of `SmallBox`.
 #[derive(Debug)]
 pub struct Envelope<M = AnyMessage> {
-    created_time: Instant, // Now used also as a sent time.
+    // The creation time until the envelope is enqueued into a mailbox.
+    enqueued_time: Instant,
     trace_id: TraceId,
     kind: MessageKind,
     message: M,
@@ -41,7 +42,7 @@ impl<M> Envelope<M> {
     #[inline]
     pub fn with_trace_id(message: M, kind: MessageKind, trace_id: TraceId) -> Self {
         Self {
-            created_time: Instant::now(),
+            enqueued_time: Instant::now(),
             trace_id,
             kind,
             message,
@@ -64,8 +65,12 @@ impl<M> Envelope<M> {
         &self.kind
     }
 
-    pub(crate) fn created_time(&self) -> Instant {
-        self.created_time
+    pub(crate) fn enqueued_time(&self) -> Instant {
+        self.enqueued_time
+    }
+
+    pub(crate) fn mark_enqueued(&mut self) {
+        self.enqueued_time = Instant::now();
     }
 
     #[inline]
@@ -84,7 +89,7 @@ impl<M: Message> Envelope<M> {
     #[doc(hidden)]
     pub fn upcast(self) -> Envelope {
         Envelope {
-            created_time: self.created_time,
+            enqueued_time: self.enqueued_time,
             trace_id: self.trace_id,
             kind: self.kind,
             message: self.message.upcast(),
@@ -107,7 +112,7 @@ impl Envelope {
     #[stability::unstable]
     pub fn duplicate(&self) -> Self {
         Self {
-            created_time: self.created_time,
+            enqueued_time: self.enqueued_time,
             trace_id: self.trace_id,
             kind: match &self.kind {
                 MessageKind::Regular { sender } => MessageKind::Regular { sender: *sender },
@@ -122,6 +127,16 @@ impl Envelope {
         }
     }
 
+    /// Remembers the local recipient of the request to attribute errors.
+    pub(crate) fn set_recipient(&mut self, recipient: Addr) {
+        match &mut self.kind {
+            MessageKind::RequestAny(token) | MessageKind::RequestAll(token) => {
+                token.on_delivered(recipient)
+            }
+            _ => {}
+        }
+    }
+
     // TODO: remove the method?
     pub(crate) fn set_message<M: Message>(&mut self, message: M) {
         self.message = message.upcast();
@@ -83,8 +83,58 @@ pub struct StartGroupError {
 }
 
 #[derive(Debug, Display, Error)]
-#[display(fmt = "mailbox closed")]
-pub struct SendError<T>(#[error(not(source))] pub T);
+pub enum SendError<T> {
+    /// The mailbox is full and `system.mailbox.on_overflow` is `Reject`.
+    #[display(fmt = "mailbox full")]
+    Full(#[error(not(source))] T),
+    /// The mailbox has been closed.
+    #[display(fmt = "mailbox closed")]
+    Closed(#[error(not(source))] T),
+    /// No healthy actor has been found to receive the message,
+    /// see `Outcome::HealthyUnicast`.
+    #[display(fmt = "no healthy recipients")]
+    Unhealthy(#[error(not(source))] T),
+}
+
+impl<T> SendError<T> {
+    /// Converts the error into its inner value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Closed(inner) => inner,
+            Self::Full(inner) => inner,
+            Self::Unhealthy(inner) => inner,
+        }
+    }
+
+    /// Transforms the inner message.
+    #[inline]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> SendError<U> {
+        match self {
+            Self::Full(inner) => SendError::Full(f(inner)),
+            Self::Closed(inner) => SendError::Closed(f(inner)),
+            Self::Unhealthy(inner) => SendError::Unhealthy(f(inner)),
+        }
+    }
+
+    /// Returns whether the error is the `Full` variant.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        matches!(self, Self::Full(_))
+    }
+
+    /// Returns whether the error is the `Closed` variant.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Closed(_))
+    }
+
+    /// Returns whether the error is the `Unhealthy` variant.
+    #[inline]
+    pub fn is_unhealthy(&self) -> bool {
+        matches!(self, Self::Unhealthy(_))
+    }
+}
 
 #[derive(Debug, Display, Error)]
 pub enum TrySendError<T> {
@@ -94,6 +144,10 @@ pub enum TrySendError<T> {
     /// The mailbox has been closed.
     #[display(fmt = "mailbox closed")]
     Closed(#[error(not(source))] T),
+    /// No healthy actor has been found to receive the message,
+    /// see `Outcome::HealthyUnicast`.
+    #[display(fmt = "no healthy recipients")]
+    Unhealthy(#[error(not(source))] T),
 }
 
 impl<T> TrySendError<T> {
@@ -103,6 +157,7 @@ impl<T> TrySendError<T> {
         match self {
             Self::Closed(inner) => inner,
             Self::Full(inner) => inner,
+            Self::Unhealthy(inner) => inner,
         }
     }
 
@@ -112,6 +167,7 @@ impl<T> TrySendError<T> {
         match self {
             Self::Full(inner) => TrySendError::Full(f(inner)),
             Self::Closed(inner) => TrySendError::Closed(f(inner)),
+            Self::Unhealthy(inner) => TrySendError::Unhealthy(f(inner)),
         }
     }
 
@@ -126,8 +182,18 @@ impl<T> TrySendError<T> {
     pub fn is_closed(&self) -> bool {
         matches!(self, Self::Closed(_))
     }
+
+    /// Returns whether the error is the `Unhealthy` variant.
+    #[inline]
+    pub fn is_unhealthy(&self) -> bool {
+        matches!(self, Self::Unhealthy(_))
+    }
 }
 
+#[derive(Debug, Display, Error)]
+#[display(fmt = "stash full")]
+pub struct StashError<T>(#[error(not(source))] pub T);
+
 #[derive(Debug, Display, Error)]
 pub enum RequestError {
     /// Receiver hasn't got the request.
@@ -136,6 +202,9 @@ pub enum RequestError {
     /// Receiver has got the request, but ignored it.
     #[display(fmt = "request ignored")]
     Ignored,
+    /// Receiver hasn't responded before the deadline.
+    #[display(fmt = "request timed out")]
+    Timeout,
 }
 
 impl RequestError {
@@ -150,6 +219,12 @@ impl RequestError {
     pub fn is_ignored(&self) -> bool {
         matches!(self, Self::Ignored)
     }
+
+    /// Returns whether the error is the `Timeout` variant.
+    #[inline]
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout)
+    }
 }
 
 #[derive(Debug, Clone, Display, Error)]
@@ -1,14 +1,17 @@
-use std::{fmt::Debug, future::Future, marker::PhantomData, sync::Arc};
+use std::{fmt::Debug, future::Future, marker::PhantomData, sync::Arc, time::Duration};
 
 use futures::future::BoxFuture;
 
 use crate::{
     config::Config,
     context::Context,
+    contract::Contract,
     envelope::Envelope,
     exec::{Exec, ExecResult},
+    message::Message,
+    messages::ActorStatusReport,
     object::{GroupHandle, GroupVisitor, Object},
-    restarting::RestartPolicy,
+    restarting::{RestartIntensity, RestartPolicy, RestartStrategy},
     routers::Router,
     runtime::RuntimeManager,
     supervisor::Supervisor,
@@ -16,21 +19,31 @@ use crate::{
 
 #[derive(Debug)]
 pub struct ActorGroup<R, C> {
-    restart_policy: RestartPolicy,
-    termination_policy: TerminationPolicy,
+    settings: GroupSettings,
     stop_order: i8,
+    contract: Contract,
     router: R,
     _config: PhantomData<C>,
 }
 
+/// Settings of the group passed to its supervisor.
+#[derive(Debug, Default)]
+pub(crate) struct GroupSettings {
+    pub(crate) restart_policy: RestartPolicy,
+    pub(crate) restart_intensity: Option<RestartIntensity>,
+    pub(crate) restart_strategy: RestartStrategy,
+    pub(crate) termination_policy: TerminationPolicy,
+    pub(crate) idle_timeout: Option<Duration>,
+}
+
 impl ActorGroup<(), ()> {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
-            restart_policy: RestartPolicy::default(),
-            termination_policy: TerminationPolicy::default(),
+            settings: GroupSettings::default(),
             router: (),
             stop_order: 0,
+            contract: Contract::default(),
             _config: PhantomData,
         }
     }
@@ -39,10 +52,10 @@ impl ActorGroup<(), ()> {
 impl<R, C> ActorGroup<R, C> {
     pub fn config<C1: Config>(self) -> ActorGroup<R, C1> {
         ActorGroup {
-            restart_policy: self.restart_policy,
-            termination_policy: self.termination_policy,
+            settings: self.settings,
             router: self.router,
             stop_order: self.stop_order,
+            contract: self.contract,
             _config: PhantomData,
         }
     }
@@ -51,7 +64,28 @@ impl<R, C> ActorGroup<R, C> {
     ///
     /// `RestartPolicy::never` is used by default.
     pub fn restart_policy(mut self, policy: RestartPolicy) -> Self {
-        self.restart_policy = policy;
+        self.settings.restart_policy = policy;
+        self
+    }
+
+    /// Limits the number of restarts across all actors of the group.
+    /// Once the limit is exceeded, the group is considered failed,
+    /// a failed `ActorStatusReport` for the group is sent to status
+    /// subscribers and the escalation is applied.
+    ///
+    /// Can be overridden by the `system.restart_intensity` config parameter.
+    /// Unlimited by default.
+    pub fn restart_intensity(mut self, intensity: RestartIntensity) -> Self {
+        self.settings.restart_intensity = Some(intensity);
+        self
+    }
+
+    /// Defines which actors are restarted when one of them fails.
+    /// Useful if actors share some resource and must be restarted together.
+    ///
+    /// `RestartStrategy::OneForOne` is used by default.
+    pub fn restart_strategy(mut self, strategy: RestartStrategy) -> Self {
+        self.settings.restart_strategy = strategy;
         self
     }
 
@@ -59,17 +93,37 @@ impl<R, C> ActorGroup<R, C> {
     ///
     /// `TerminationPolicy::closing` is used by default.
     pub fn termination_policy(mut self, policy: TerminationPolicy) -> Self {
-        self.termination_policy = policy;
+        self.settings.termination_policy = policy;
+        self
+    }
+
+    /// Evicts actors that haven't received messages for the specified time.
+    /// The actor's mailbox is closed, so it terminates as usual, and the next
+    /// message routed to its key starts it again with
+    /// [`ActorStartCause::Evicted`].
+    ///
+    /// Useful for keyed groups, where actors are created on demand and
+    /// otherwise live until the whole group terminates.
+    ///
+    /// Messages sent to the actor while it's terminating aren't rejected,
+    /// the actor is started again right after termination to handle them.
+    ///
+    /// Can be overridden by the `system.idle_timeout` config parameter.
+    /// Disabled by default.
+    ///
+    /// [`ActorStartCause::Evicted`]: crate::ActorStartCause::Evicted
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.settings.idle_timeout = Some(timeout);
         self
     }
 
     /// Installs a router.
     pub fn router<R1: Router<C>>(self, router: R1) -> ActorGroup<R1, C> {
         ActorGroup {
-            restart_policy: self.restart_policy,
-            termination_policy: self.termination_policy,
+            settings: self.settings,
             router,
             stop_order: self.stop_order,
+            contract: self.contract,
             _config: self._config,
         }
     }
@@ -92,6 +146,39 @@ impl<R, C> ActorGroup<R, C> {
         self
     }
 
+    /// Declares that the group handles messages of the specified type.
+    ///
+    /// Declarations are optional and used only by [`check_only()`] to verify
+    /// routes: groups without them are considered to accept any message.
+    ///
+    /// # Panics
+    /// If `M` is `AnyMessage`.
+    ///
+    /// [`check_only()`]: crate::init::check_only
+    #[track_caller]
+    pub fn accepts<M: Message>(mut self) -> Self {
+        self.contract.add_accepted::<M>();
+        self
+    }
+
+    /// Declares that the group sends messages of the specified type using
+    /// routing (e.g. `Context::send()`), not to specific addresses.
+    ///
+    /// Declarations are optional and used only by [`check_only()`] to verify
+    /// that every emitted message has a route to a group accepting it.
+    /// Groups with routes to remote groups aren't verified, because remote
+    /// groups have no declarations.
+    ///
+    /// # Panics
+    /// If `M` is `AnyMessage`.
+    ///
+    /// [`check_only()`]: crate::init::check_only
+    #[track_caller]
+    pub fn emits<M: Message>(mut self) -> Self {
+        self.contract.add_emitted::<M>();
+        self
+    }
+
     /// Builds the group with the specified executor function.
     pub fn exec<X, O, ER>(self, exec: X) -> Blueprint
     where
@@ -108,8 +195,7 @@ impl<R, C> ActorGroup<R, C> {
                 name,
                 exec,
                 self.router,
-                self.restart_policy,
-                self.termination_policy,
+                self.settings,
                 rt_manager,
             ));
 
@@ -119,6 +205,7 @@ impl<R, C> ActorGroup<R, C> {
         Blueprint {
             mount: Box::new(mount),
             stop_order: self.stop_order,
+            contract: self.contract,
         }
     }
 }
@@ -139,11 +226,20 @@ where
     fn finished(&self) -> BoxFuture<'static, ()> {
         self.0.finished()
     }
+
+    fn aborted(&self) -> usize {
+        self.0.aborted()
+    }
+
+    fn alive(&self) -> Vec<ActorStatusReport> {
+        self.0.alive()
+    }
 }
 
 pub struct Blueprint {
     pub(crate) mount: Box<dyn FnOnce(Context, String, RuntimeManager) -> Object>,
     pub(crate) stop_order: i8,
+    pub(crate) contract: Contract,
 }
 
 /// The behaviour on the `Terminate` message.
@@ -151,6 +247,7 @@ pub struct Blueprint {
 pub struct TerminationPolicy {
     pub(crate) stop_spawning: bool,
     pub(crate) close_mailbox: bool,
+    pub(crate) deadline: Option<Duration>,
 }
 
 impl Default for TerminationPolicy {
@@ -170,6 +267,7 @@ impl TerminationPolicy {
         Self {
             stop_spawning: true,
             close_mailbox: true,
+            deadline: None,
         }
     }
 
@@ -181,6 +279,25 @@ impl TerminationPolicy {
         Self {
             stop_spawning: true,
             close_mailbox: false,
+            deadline: None,
+        }
+    }
+
+    /// Aborts actors that haven't terminated within the specified time after
+    /// the group has received `Terminate` or they have been retired by the
+    /// router. Aborted actors get the [`ActorStatusKind::Aborted`] status and
+    /// aren't restarted.
+    ///
+    /// Actors are aborted at the next `.await`, so it doesn't help with actors
+    /// blocking the thread.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`ActorStatusKind::Aborted`]: crate::ActorStatusKind::Aborted
+    pub fn deadline(self, deadline: Duration) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self
         }
     }
 
@@ -1,4 +1,4 @@
-use std::{future::Future, sync::Arc, time::Duration};
+use std::{env, fs, future::Future, path::PathBuf, sync::Arc, time::Duration};
 
 use futures::future::join_all;
 use tokio::{
@@ -20,15 +20,17 @@ use crate::{
     addr::{Addr, GroupNo},
     config::SystemConfig,
     context::Context,
+    contract,
     demux::Demux,
-    errors::{RequestError, StartError, StartGroupError},
+    errors::{StartError, StartGroupError},
     message,
     messages::{StartEntrypoint, Terminate, UpdateConfig},
     object::Object,
-    scope::{Scope, ScopeGroupShared},
+    scope::{self, Scope, ScopeGroupShared},
     signal::{Signal, SignalKind},
+    source::UnattachedSource,
     subscription::SubscriptionManager,
-    topology::{Topology, SYSTEM_INIT_GROUP_NO},
+    topology::{LocalActorGroup, Topology, SYSTEM_INIT_GROUP_NO},
     tracing::TraceId,
 };
 
@@ -53,7 +55,7 @@ async fn start_entrypoints(ctx: &Context, topology: &Topology, is_check_only: bo
             match response {
                 Ok(Ok(())) => Ok(()),
                 Ok(Err(e)) => Err(StartError::single(group.name.clone(), e.reason)),
-                Err(RequestError::Ignored) | Err(RequestError::Failed) => Err(StartError::single(
+                Err(_) => Err(StartError::single(
                     group.name.clone(),
                     "config cannot be delivered to the entrypoint".into(),
                 )),
@@ -76,7 +78,7 @@ async fn start_entrypoints(ctx: &Context, topology: &Topology, is_check_only: bo
                         .collect();
                     Err(StartError::multiple(group_errors))
                 }
-                Err(RequestError::Ignored) | Err(RequestError::Failed) => Err(StartError::single(
+                Err(_) => Err(StartError::single(
                     group.name,
                     "starting message cannot be delivered to the entrypoint".into(),
                 )),
@@ -108,10 +110,26 @@ pub async fn start(topology: Topology) {
 }
 
 /// The same as `start()`, but returns an error rather than panics.
+///
+/// If the `ELFO_DUMP_TOPOLOGY` environment variable is set, the topology is
+/// dumped to the specified file before starting. The format is chosen by the
+/// extension: Graphviz DOT for `.dot`, JSON otherwise.
 pub async fn try_start(topology: Topology) -> Result<()> {
     check_messages_uniqueness()?;
-
-    let res = do_start(topology, false, termination).await;
+    dump_topology_if_requested(&topology);
+
+    // Signals are listened from the start to handle ones received during startup.
+    let signals = [
+        SignalKind::UnixTerminate,
+        SignalKind::UnixInterrupt,
+        SignalKind::WindowsCtrlC,
+    ]
+    .map(|kind| Signal::new(kind, TerminationSignal));
+
+    let res = do_start(topology, false, |ctx, topology| {
+        termination(ctx, topology, signals)
+    })
+    .await;
 
     if res.is_err() {
         // XXX: give enough time to the logger.
@@ -123,8 +141,15 @@ pub async fn try_start(topology: Topology) -> Result<()> {
 
 /// Starts node in "check only" mode. Entrypoints are started, then the system
 /// is immediately gracefully terminated.
+///
+/// Also verifies that every message declared by `ActorGroup::emits()` has
+/// a route to a group accepting it, see `ActorGroup::accepts()`.
+///
+/// The topology can be dumped the same way as in [`try_start()`].
 pub async fn check_only(topology: Topology) -> Result<()> {
     check_messages_uniqueness()?;
+    contract::check(&topology)?;
+    dump_topology_if_requested(&topology);
 
     // The logger is not supposed to be initialized in this mode, so we do not wait
     // for it before exiting.
@@ -151,6 +176,23 @@ pub fn check_messages_uniqueness() -> Result<()> {
     })
 }
 
+const DUMP_TOPOLOGY_ENV: &str = "ELFO_DUMP_TOPOLOGY";
+
+fn dump_topology_if_requested(topology: &Topology) {
+    let path = PathBuf::from(ward!(env::var_os(DUMP_TOPOLOGY_ENV)));
+
+    let content = if path.extension().is_some_and(|ext| ext == "dot") {
+        topology.to_dot()
+    } else {
+        serde_json::to_string_pretty(&topology.snapshot()).expect("cannot serialize topology")
+    };
+
+    match fs::write(&path, content) {
+        Ok(()) => info!(path = %path.display(), "topology is dumped"),
+        Err(err) => warn!(path = %path.display(), error = %err, "cannot dump topology"),
+    }
+}
+
 #[doc(hidden)]
 pub async fn do_start<F: Future>(
     topology: Topology,
@@ -170,22 +212,25 @@ pub async fn do_start<F: Future>(
         key: "_".into(), // Just like `Singleton`.
     });
 
+    let mut config = SystemConfig::default();
+    config.logging.max_level = LevelFilter::INFO;
+
     // XXX: create a real group.
     let actor = Actor::new(
         meta.clone(),
         addr,
+        &config.mailbox,
         Default::default(),
         Arc::new(SubscriptionManager::new(ctx.clone())),
     );
 
     let scope_shared = ScopeGroupShared::new(addr);
-    let mut config = SystemConfig::default();
-    config.logging.max_level = LevelFilter::INFO;
     scope_shared.configure(&config);
 
     let scope = Scope::new(TraceId::generate(), addr, meta, Arc::new(scope_shared));
     scope.clone().sync_within(|| actor.on_start()); // need to emit initial metrics
     entry.insert(Object::new(addr, actor));
+    topology.book.set_system_init(addr);
 
     // It must be called after `entry.insert()`.
     let ctx = ctx
@@ -199,8 +244,13 @@ pub async fn do_start<F: Future>(
     scope.within(init).await
 }
 
+/// Gracefully terminates the node, sent on escalated failures.
 #[message]
-struct TerminateSystem;
+pub(crate) struct TerminateSystem;
+
+/// Gracefully terminates the node, repeated ones escalate termination.
+#[message]
+struct TerminationSignal;
 
 #[message]
 struct CheckMemoryUsageTick;
@@ -209,10 +259,14 @@ struct CheckMemoryUsageTick;
 const SEND_CLOSING_TERMINATE_AFTER: Duration = Duration::from_secs(25);
 const STOP_GROUP_TERMINATION_AFTER: Duration = Duration::from_secs(35);
 
-async fn termination(mut ctx: Context, topology: Topology) {
-    ctx.attach(Signal::new(SignalKind::UnixTerminate, TerminateSystem));
-    ctx.attach(Signal::new(SignalKind::UnixInterrupt, TerminateSystem));
-    ctx.attach(Signal::new(SignalKind::WindowsCtrlC, TerminateSystem));
+async fn termination(
+    mut ctx: Context,
+    topology: Topology,
+    signals: [UnattachedSource<Signal<TerminationSignal>>; 3],
+) {
+    for signal in signals {
+        ctx.attach(signal);
+    }
 
     #[cfg(target_os = "linux")]
     let memory_tracker = {
@@ -235,7 +289,7 @@ async fn termination(mut ctx: Context, topology: Topology) {
     let mut oom_prevented = false;
 
     while let Some(envelope) = ctx.recv().await {
-        if envelope.is::<TerminateSystem>() {
+        if envelope.is::<TerminateSystem>() || envelope.is::<TerminationSignal>() {
             break;
         }
 
@@ -267,23 +321,43 @@ async fn termination(mut ctx: Context, topology: Topology) {
 
     ctx.set_status(ActorStatus::TERMINATING);
 
-    let termination = do_termination(ctx.pruned(), topology);
+    // Termination is escalated on repeated signals:
+    // 1. Groups are terminated politely, according to their stop order.
+    // 2. `Terminate::closing` is sent to all remaining groups at once.
+    // 3. The node is terminated immediately.
+    let mut stage = 1;
+    report_unfinished(&topology, stage);
+
+    let termination = do_termination(ctx.pruned(), topology.clone());
     pin!(termination);
 
     loop {
         select! {
             _ = &mut termination => return,
             Some(envelope) = ctx.recv() => {
-                if !envelope.is::<TerminateSystem>() {
+                // Escalated failures don't escalate termination further.
+                if !envelope.is::<TerminationSignal>() {
                     continue;
                 }
 
                 if oom_prevented {
                     // Skip the first signal after OOM prevented.
                     oom_prevented = false;
+                    continue;
+                }
+
+                stage += 1;
+                let unfinished = report_unfinished(&topology, stage);
+
+                if stage == 2 {
+                    // `Ctrl-C` has been pressed again.
+                    warn!("sending closing Terminate to all remaining groups");
+                    for addr in unfinished {
+                        let _ = ctx.try_send_to(addr, Terminate::closing());
+                    }
                 } else {
-                    // `Ctrl-C` has been pressed again. Terminate immediately.
-                    // TODO: `Terminate::closing` on second `Ctrl-C`
+                    // `Ctrl-C` has been pressed for the third time.
+                    error!("terminating immediately, remaining actors are aborted");
                     return;
                 }
             }
@@ -291,35 +365,125 @@ async fn termination(mut ctx: Context, topology: Topology) {
     }
 }
 
+/// Logs groups and actors that haven't finished yet along with their statuses.
+/// Returns addresses of such groups.
+fn report_unfinished(topology: &Topology, stage: u32) -> Vec<Addr> {
+    let mut unfinished = Vec::new();
+
+    for group in topology.locals() {
+        let object = ward!(topology.book.get_owned(group.addr), continue);
+        let actors = object.alive_actors();
+        if actors.is_empty() {
+            continue;
+        }
+
+        let actors = actors
+            .iter()
+            .map(|report| format!("{} ({})", report.meta.key, report.status))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        warn!(%stage, group = %group.name, %actors, "actor group hasn't finished yet");
+        unfinished.push(group.addr);
+    }
+
+    if unfinished.is_empty() {
+        info!(%stage, "all actor groups have finished");
+    }
+
+    unfinished
+}
+
 async fn do_termination(ctx: Context, topology: Topology) {
-    let mut stop_order_list = topology
-        .locals()
+    let groups = topology.locals().collect();
+    terminate_groups_in_order(&ctx, &topology, groups, "termination").await;
+}
+
+/// Terminates a group unmounted at runtime.
+///
+/// Unlike the node's termination, it can be called outside the actor system,
+/// in which case the termination is performed in the scope of `system.init`.
+pub(crate) async fn terminate_unmounted(topology: &Topology, group: LocalActorGroup) {
+    let ctx = Context::new(topology.book.clone(), Demux::default());
+    let terminate = terminate_groups_in_order(&ctx, topology, vec![group], "unmount");
+
+    if scope::try_expose().is_some() {
+        return terminate.await;
+    }
+
+    let addr = topology.book.system_init().unwrap_or(Addr::NULL);
+    let meta = Arc::new(ActorMeta {
+        group: INIT_GROUP_NAME.into(),
+        key: "_".into(),
+    });
+    let group_shared = Arc::new(ScopeGroupShared::new(addr));
+    let scope = Scope::new(TraceId::generate(), addr, meta, group_shared);
+    scope.within(terminate).await
+}
+
+/// Terminates groups according to their stop order.
+/// Also used to unmount groups at runtime, `reason` distinguishes these cases
+/// in logs.
+pub(crate) async fn terminate_groups_in_order(
+    ctx: &Context,
+    topology: &Topology,
+    groups: Vec<LocalActorGroup>,
+    reason: &str,
+) {
+    let mut stop_order_list = groups
+        .iter()
         .map(|group| group.stop_order)
         .collect::<Vec<_>>();
 
     stop_order_list.sort_unstable();
     stop_order_list.dedup();
 
+    let mut aborted = Vec::new();
     for stop_order in stop_order_list {
-        info!(%stop_order, "terminating groups");
-        terminate_groups(&ctx, &topology, stop_order).await;
+        info!(%reason, %stop_order, "terminating groups");
+        let groups = groups
+            .iter()
+            .filter(|group| group.stop_order == stop_order)
+            .cloned();
+        aborted.extend(terminate_groups(ctx, topology, groups).await);
+    }
+
+    if aborted.is_empty() {
+        info!(%reason, "all groups are terminated gracefully");
+    } else {
+        let groups = aborted
+            .iter()
+            .map(|(name, count)| format!("{name} ({count})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        error!(%reason, %groups, "some actors are aborted after the termination deadline");
     }
 }
 
-async fn terminate_groups(ctx: &Context, topology: &Topology, stop_order: i8) {
-    let futures = topology
-        .locals()
-        .filter(|group| group.stop_order == stop_order)
+/// Returns names of groups with actors aborted after the termination deadline
+/// along with the number of such actors.
+async fn terminate_groups(
+    ctx: &Context,
+    topology: &Topology,
+    groups: impl Iterator<Item = LocalActorGroup>,
+) -> Vec<(String, usize)> {
+    let futures = groups
         .map(|group| async move {
             let started_at = Instant::now();
             select! {
                 _ = terminate_group(ctx, group.addr, group.name.clone(), started_at) => {},
-                _ = watch_group(ctx, group.addr, group.name, started_at) => {},
+                _ = watch_group(ctx, group.addr, group.name.clone(), started_at) => {},
             }
+
+            let aborted = topology
+                .book
+                .get_owned(group.addr)
+                .map_or(0, |object| object.aborted());
+            (aborted > 0).then_some((group.name, aborted))
         })
         .collect::<Vec<_>>();
 
-    join_all(futures).await;
+    join_all(futures).await.into_iter().flatten().collect()
 }
 
 async fn terminate_group(ctx: &Context, addr: Addr, name: String, started_at: Instant) {
@@ -11,7 +11,9 @@ extern crate self as elfo_core;
 
 // TODO: revise this list
 pub use crate::{
-    actor::{ActorMeta, ActorStartCause, ActorStartInfo, ActorStatus, ActorStatusKind},
+    actor::{
+        ActorMeta, ActorStartCause, ActorStartInfo, ActorStatus, ActorStatusKind, PreviousRun,
+    },
     addr::Addr,
     config::Config,
     context::{Context, RequestBuilder},
@@ -20,7 +22,9 @@ pub use crate::{
     local::{Local, MoveOwnership},
     message::{Message, Request},
     request_table::ResponseToken,
-    restarting::{RestartParams, RestartPolicy},
+    restarting::{
+        Escalation, Jitter, RestartIntensity, RestartParams, RestartPolicy, RestartStrategy,
+    },
     source::{SourceHandle, UnattachedSource},
     topology::Topology,
 };
@@ -51,6 +55,8 @@ mod actor;
 mod addr;
 mod address_book;
 mod context;
+mod contract;
+mod dead_letters;
 mod demux;
 mod envelope;
 mod exec;
@@ -1,88 +1,687 @@
-use futures_intrusive::{
-    buffer::GrowingHeapBuf,
-    channel::{self, GenericChannel},
+use std::{
+    collections::VecDeque,
+    mem,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
 };
-use parking_lot::{Mutex, RawMutex};
+
+use metrics::increment_counter;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use tokio::{sync::Notify, time::Instant};
 
 use crate::{
     envelope::Envelope,
     errors::{SendError, TrySendError},
+    message::Message,
     tracing::TraceId,
 };
 
-// TODO: make mailboxes bounded by time instead of size.
-const LIMIT: usize = 100_000;
+// === MailboxConfig ===
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub(crate) struct MailboxConfig {
+    pub(crate) capacity: usize,
+    pub(crate) on_overflow: OverflowPolicy,
+    /// Envelopes waiting longer are discarded instead of being received.
+    #[serde(with = "humantime_serde")]
+    pub(crate) max_age: Option<Duration>,
+    /// The limit of envelopes deferred by `Context::stash()`.
+    pub(crate) stash_capacity: usize,
+}
+
+impl Default for MailboxConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 100_000,
+            on_overflow: OverflowPolicy::Block,
+            max_age: None,
+            stash_capacity: 10_000,
+        }
+    }
+}
+
+/// What to do with an incoming envelope if the mailbox is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(crate) enum OverflowPolicy {
+    /// `send()` waits for free space, `try_send()` returns `Full`.
+    Block,
+    /// Both `send()` and `try_send()` fail immediately with `Full`.
+    Reject,
+    /// The oldest envelope in the mailbox is discarded to make room.
+    DropOldest,
+    /// The incoming envelope is discarded.
+    DropNewest,
+}
+
+impl OverflowPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Block => "Block",
+            Self::Reject => "Reject",
+            Self::DropOldest => "DropOldest",
+            Self::DropNewest => "DropNewest",
+        }
+    }
+}
+
+// === Mailbox ===
 
+/// Consists of three queues, drained in the following order:
+/// * for built-in control messages (`Terminate`, `UpdateConfig` and so on),
+///   which isn't limited by the capacity, so control messages are never
+///   rejected and aren't stuck behind a backlog of regular ones.
+/// * for user messages marked by `#[message(priority = "high")]`.
+/// * for all other messages.
+///
+/// The capacity and the overflow policy are applied to the last two queues
+/// separately.
 pub(crate) struct Mailbox {
-    queue: GenericChannel<RawMutex, Envelope, GrowingHeapBuf<Envelope>>,
-    closed_trace_id: Mutex<Option<TraceId>>,
+    control: Mutex<Control>,
+    /// The number of envelopes in all queues, readable without locking.
+    len: AtomicUsize,
+    rx_notify: Notify,
+    /// Senders blocked on the regular queue.
+    tx_notify: Notify,
+    /// Senders blocked on the high priority queue.
+    high_tx_notify: Notify,
+}
+
+struct Control {
+    control_queue: VecDeque<Envelope>,
+    high_queue: VecDeque<Envelope>,
+    queue: VecDeque<Envelope>,
+    config: MailboxConfig,
+    closed_trace_id: Option<TraceId>,
+    /// Set once evicted. The receiver gets `Closed`, but envelopes are still
+    /// accepted to be passed to the next actor, see `close_evicted()`.
+    evicted_trace_id: Option<TraceId>,
+    /// When the receiver started waiting for envelopes, `None` if it's busy.
+    /// Control envelopes (e.g. `Ping`) don't reset it.
+    idle_since: Option<Instant>,
+}
+
+/// The result of [`Mailbox::close_if_idle()`].
+pub(crate) enum IdleCheck {
+    /// The mailbox has been closed due to inactivity.
+    Evicted,
+    /// The mailbox has already been closed.
+    Closed,
+    /// The receiver is active, the check should be repeated at this instant.
+    Active(Instant),
+}
+
+enum PushResult {
+    /// Contains an envelope evicted according to `OverflowPolicy`.
+    Pushed(Option<Envelope>),
+    Full(Envelope),
+    Closed(Envelope),
 }
 
 impl Mailbox {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(config: &MailboxConfig) -> Self {
         Self {
-            queue: GenericChannel::with_capacity(LIMIT),
-            closed_trace_id: Mutex::new(None),
+            control: Mutex::new(Control {
+                control_queue: VecDeque::new(),
+                high_queue: VecDeque::new(),
+                queue: VecDeque::new(),
+                config: config.clone(),
+                closed_trace_id: None,
+                evicted_trace_id: None,
+                idle_since: None,
+            }),
+            len: AtomicUsize::new(0),
+            rx_notify: Notify::new(),
+            tx_notify: Notify::new(),
+            high_tx_notify: Notify::new(),
         }
     }
 
-    pub(crate) async fn send(&self, envelope: Envelope) -> Result<(), SendError<Envelope>> {
-        let fut = self.queue.send(envelope);
-        fut.await.map_err(|err| SendError(err.0))
+    /// Applies the new config. Envelopes that are already in the mailbox
+    /// are kept even if the capacity is reduced.
+    pub(crate) fn set_config(&self, config: &MailboxConfig) {
+        let mut control = self.control.lock();
+        if control.config == *config {
+            return;
+        }
+
+        control.config = config.clone();
+        drop(control);
+
+        // Blocked senders should recheck the capacity and the policy.
+        self.notify_all_senders();
+    }
+
+    fn notify_all_senders(&self) {
+        self.tx_notify.notify_waiters();
+        self.high_tx_notify.notify_waiters();
+    }
+
+    /// Can be outdated, so it's suitable only for heuristics like routing.
+    pub(crate) fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    /// Must be called under the lock after changing queues.
+    fn update_len(&self, control: &Control) {
+        let len = control.control_queue.len() + control.high_queue.len() + control.queue.len();
+        self.len.store(len, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stash_capacity(&self) -> usize {
+        self.control.lock().config.stash_capacity
+    }
+
+    pub(crate) async fn send(&self, mut envelope: Envelope) -> Result<(), SendError<Envelope>> {
+        loop {
+            let waiting = {
+                let mut control = self.control.lock();
+
+                match push(&mut control, envelope) {
+                    PushResult::Pushed(evicted) => {
+                        self.update_len(&control);
+                        drop(control);
+                        self.rx_notify.notify_one();
+                        drop(evicted);
+                        return Ok(());
+                    }
+                    PushResult::Full(returned) => {
+                        if control.config.on_overflow != OverflowPolicy::Block {
+                            return Err(SendError::Full(returned));
+                        }
+
+                        let notify = if returned.message().high_priority() {
+                            &self.high_tx_notify
+                        } else {
+                            &self.tx_notify
+                        };
+
+                        envelope = returned;
+                        notify.notified()
+                    }
+                    PushResult::Closed(returned) => return Err(SendError::Closed(returned)),
+                }
+            };
+
+            waiting.await;
+        }
     }
 
     pub(crate) fn try_send(&self, envelope: Envelope) -> Result<(), TrySendError<Envelope>> {
-        self.queue.try_send(envelope).map_err(|err| match err {
-            channel::TrySendError::Full(envelope) => TrySendError::Full(envelope),
-            channel::TrySendError::Closed(envelope) => TrySendError::Closed(envelope),
-        })
+        let mut control = self.control.lock();
+
+        match push(&mut control, envelope) {
+            PushResult::Pushed(evicted) => {
+                self.update_len(&control);
+                drop(control);
+                self.rx_notify.notify_one();
+                drop(evicted);
+                Ok(())
+            }
+            PushResult::Full(envelope) => Err(TrySendError::Full(envelope)),
+            PushResult::Closed(envelope) => Err(TrySendError::Closed(envelope)),
+        }
     }
 
     pub(crate) async fn recv(&self) -> RecvResult {
-        let fut = self.queue.receive();
-        match fut.await {
-            Some(envelope) => RecvResult::Data(envelope),
-            None => self.on_close(),
+        loop {
+            let waiting = {
+                let mut control = self.control.lock();
+                let result = self.pop(&mut control);
+                self.update_len(&control);
+                if let Some(result) = result {
+                    return result;
+                }
+
+                if control.idle_since.is_none() {
+                    control.idle_since = Some(Instant::now());
+                }
+
+                self.rx_notify.notified()
+            };
+
+            waiting.await;
         }
     }
 
     pub(crate) fn try_recv(&self) -> Option<RecvResult> {
-        match self.queue.try_receive() {
-            Ok(envelope) => Some(RecvResult::Data(envelope)),
-            Err(channel::TryReceiveError::Empty) => None,
-            Err(channel::TryReceiveError::Closed) => Some(self.on_close()),
-        }
+        let mut control = self.control.lock();
+        let result = self.pop(&mut control);
+        self.update_len(&control);
+        result
     }
 
     #[cold]
     pub(crate) fn close(&self, trace_id: TraceId) -> bool {
-        // NOTE: It is important that we take the lock here before actually closing the
-        // channel. If we take a lock after closing the channel, data race is
-        // possible when we try to `recv()` after the channel is closed, but
-        // before the `closed_trace_id` is assigned.
-        let mut closed_trace_id = self.closed_trace_id.lock();
-        if self.queue.close().is_newly_closed() {
-            *closed_trace_id = Some(trace_id);
-            true
-        } else {
-            false
+        let mut control = self.control.lock();
+        // Evicted mailboxes are closed only by `close_evicted()`.
+        if control.closed_trace_id.is_some() || control.evicted_trace_id.is_some() {
+            return false;
+        }
+
+        control.closed_trace_id = Some(trace_id);
+        drop(control);
+
+        self.rx_notify.notify_one();
+        self.notify_all_senders();
+        true
+    }
+
+    /// Resets the idle timer, used for envelopes received bypassing the mailbox.
+    pub(crate) fn mark_active(&self) {
+        self.control.lock().idle_since = None;
+    }
+
+    /// Closes the mailbox for the receiver if it's empty and the receiver has
+    /// been waiting for envelopes for at least `timeout`.
+    pub(crate) fn close_if_idle(&self, timeout: Duration, trace_id: TraceId) -> IdleCheck {
+        let mut control = self.control.lock();
+        if control.closed_trace_id.is_some() || control.evicted_trace_id.is_some() {
+            return IdleCheck::Closed;
+        }
+
+        let now = Instant::now();
+        let is_empty = control.control_queue.is_empty()
+            && control.high_queue.is_empty()
+            && control.queue.is_empty();
+
+        match control.idle_since {
+            Some(since) if is_empty && now >= since + timeout => {
+                control.evicted_trace_id = Some(trace_id);
+                drop(control);
+
+                self.rx_notify.notify_one();
+                IdleCheck::Evicted
+            }
+            Some(since) if is_empty => IdleCheck::Active(since + timeout),
+            _ => IdleCheck::Active(now + timeout),
         }
     }
 
+    /// Closes the evicted mailbox if no envelopes have been sent after eviction.
+    /// Otherwise, envelopes are still accepted until `close_evicted()`.
+    pub(crate) fn try_close_evicted(&self) -> bool {
+        let mut control = self.control.lock();
+        debug_assert!(control.evicted_trace_id.is_some());
+
+        let is_empty = control.control_queue.is_empty()
+            && control.high_queue.is_empty()
+            && control.queue.is_empty();
+
+        if is_empty && control.closed_trace_id.is_none() {
+            control.closed_trace_id = control.evicted_trace_id;
+        }
+
+        is_empty
+    }
+
+    /// Closes the evicted mailbox and returns envelopes sent after eviction.
     #[cold]
-    pub(crate) fn drop_all(&self) {
-        while self.queue.try_receive().is_ok() {}
+    pub(crate) fn close_evicted(&self) -> Vec<Envelope> {
+        let mut control = self.control.lock();
+        debug_assert!(control.evicted_trace_id.is_some());
+
+        if control.closed_trace_id.is_none() {
+            control.closed_trace_id = control.evicted_trace_id;
+        }
+
+        let control_queue = mem::take(&mut control.control_queue);
+        let high_queue = mem::take(&mut control.high_queue);
+        let queue = mem::take(&mut control.queue);
+        self.update_len(&control);
+        drop(control);
+
+        self.notify_all_senders();
+        control_queue
+            .into_iter()
+            .chain(high_queue)
+            .chain(queue)
+            .collect()
     }
 
     #[cold]
-    fn on_close(&self) -> RecvResult {
-        let trace_id = self.closed_trace_id.lock().expect("called before close()");
-        RecvResult::Closed(trace_id)
+    pub(crate) fn drop_all(&self) {
+        // Drop envelopes outside the lock, because it can resolve requests.
+        let mut control = self.control.lock();
+
+        // Envelopes of evicted mailboxes are passed further, see `close_evicted()`.
+        if control.evicted_trace_id.is_some() && control.closed_trace_id.is_none() {
+            return;
+        }
+
+        let control_queue = mem::take(&mut control.control_queue);
+        let high_queue = mem::take(&mut control.high_queue);
+        let queue = mem::take(&mut control.queue);
+        self.update_len(&control);
+        drop(control);
+
+        drop(control_queue);
+        drop(high_queue);
+        drop(queue);
+        self.notify_all_senders();
+    }
+
+    fn pop(&self, control: &mut Control) -> Option<RecvResult> {
+        // The evicted receiver stops immediately, the rest is passed further.
+        if let Some(trace_id) = control.evicted_trace_id {
+            return Some(RecvResult::Closed(trace_id));
+        }
+
+        if let Some(envelope) = control.control_queue.pop_front() {
+            // Control envelopes are never stale.
+            return Some(RecvResult::Data(envelope));
+        }
+
+        let capacity = control.config.capacity;
+        let (is_high, queue) = if control.high_queue.is_empty() {
+            (false, &mut control.queue)
+        } else {
+            (true, &mut control.high_queue)
+        };
+
+        let was_full = queue.len() >= capacity;
+
+        match queue.pop_front() {
+            Some(envelope) => {
+                control.idle_since = None;
+
+                if was_full {
+                    if is_high {
+                        self.high_tx_notify.notify_one();
+                    } else {
+                        self.tx_notify.notify_one();
+                    }
+                }
+
+                // High priority envelopes are never stale.
+                if !is_high && is_stale(&envelope, control.config.max_age) {
+                    Some(RecvResult::Stale(envelope))
+                } else {
+                    Some(RecvResult::Data(envelope))
+                }
+            }
+            None => control.closed_trace_id.map(RecvResult::Closed),
+        }
+    }
+}
+
+fn is_stale(envelope: &Envelope, max_age: Option<Duration>) -> bool {
+    let max_age = ward!(max_age, return false);
+
+    envelope.enqueued_time().elapsed() > max_age
+}
+
+fn push(control: &mut Control, mut envelope: Envelope) -> PushResult {
+    if control.closed_trace_id.is_some() {
+        return PushResult::Closed(envelope);
+    }
+
+    envelope.mark_enqueued();
+
+    let message = envelope.message();
+    let queue = if !message.high_priority() {
+        &mut control.queue
+    } else if message.control() {
+        control.control_queue.push_back(envelope);
+        return PushResult::Pushed(None);
+    } else {
+        &mut control.high_queue
+    };
+
+    if queue.len() < control.config.capacity {
+        queue.push_back(envelope);
+        return PushResult::Pushed(None);
+    }
+
+    match control.config.on_overflow {
+        OverflowPolicy::Block | OverflowPolicy::Reject => PushResult::Full(envelope),
+        OverflowPolicy::DropOldest => {
+            let evicted = queue.pop_front();
+            queue.push_back(envelope);
+            on_dropped(OverflowPolicy::DropOldest);
+            PushResult::Pushed(evicted)
+        }
+        OverflowPolicy::DropNewest => {
+            on_dropped(OverflowPolicy::DropNewest);
+            PushResult::Pushed(Some(envelope))
+        }
     }
 }
 
+/// Counted in the sender's scope, because it's the one that causes overflow.
+#[cold]
+fn on_dropped(policy: OverflowPolicy) {
+    increment_counter!("elfo_dropped_messages_total", "policy" => policy.as_str());
+}
+
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum RecvResult {
     Data(Envelope),
+    /// Waited in the mailbox longer than `max_age`, must be discarded.
+    Stale(Envelope),
     Closed(TraceId),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use elfo_utils::time::with_instant_mock;
+
+    use crate::{envelope::MessageKind, message, messages::Terminate, Addr};
+
+    #[message]
+    struct Num(u32);
+
+    fn envelope(num: u32) -> Envelope {
+        envelope_of(Num(num))
+    }
+
+    fn envelope_of(message: impl Message) -> Envelope {
+        let kind = MessageKind::Regular { sender: Addr::NULL };
+        Envelope::with_trace_id(message, kind, TraceId::try_from(1).unwrap()).upcast()
+    }
+
+    fn mailbox(capacity: usize, on_overflow: OverflowPolicy) -> Mailbox {
+        Mailbox::new(&MailboxConfig {
+            capacity,
+            on_overflow,
+            max_age: None,
+            ..Default::default()
+        })
+    }
+
+    fn recv_all(mailbox: &Mailbox) -> Vec<u32> {
+        let mut nums = Vec::new();
+        while let Some(RecvResult::Data(envelope)) = mailbox.try_recv() {
+            nums.push(envelope.message().downcast_ref::<Num>().unwrap().0);
+        }
+        nums
+    }
+
+    #[test]
+    fn reject() {
+        for policy in [OverflowPolicy::Block, OverflowPolicy::Reject] {
+            let mailbox = mailbox(2, policy);
+            assert!(mailbox.try_send(envelope(1)).is_ok());
+            assert!(mailbox.try_send(envelope(2)).is_ok());
+            assert!(mailbox.try_send(envelope(3)).unwrap_err().is_full());
+            assert_eq!(recv_all(&mailbox), vec![1, 2]);
+        }
+    }
+
+    #[tokio::test]
+    async fn reject_on_send() {
+        let mailbox = mailbox(1, OverflowPolicy::Reject);
+        mailbox.send(envelope(1)).await.unwrap();
+        assert!(mailbox.send(envelope(2)).await.unwrap_err().is_full());
+
+        mailbox.close(TraceId::try_from(1).unwrap());
+        assert!(mailbox.send(envelope(3)).await.unwrap_err().is_closed());
+    }
+
+    #[test]
+    fn drop_oldest() {
+        let mailbox = mailbox(2, OverflowPolicy::DropOldest);
+        for i in 1..=4 {
+            assert!(mailbox.try_send(envelope(i)).is_ok());
+        }
+        assert_eq!(mailbox.len(), 2);
+        assert_eq!(recv_all(&mailbox), vec![3, 4]);
+    }
+
+    #[test]
+    fn drop_newest() {
+        let mailbox = mailbox(2, OverflowPolicy::DropNewest);
+        for i in 1..=4 {
+            assert!(mailbox.try_send(envelope(i)).is_ok());
+        }
+        assert_eq!(recv_all(&mailbox), vec![1, 2]);
+    }
+
+    #[test]
+    fn set_capacity() {
+        let mailbox = mailbox(1, OverflowPolicy::Reject);
+        assert!(mailbox.try_send(envelope(1)).is_ok());
+        assert!(mailbox.try_send(envelope(2)).unwrap_err().is_full());
+
+        mailbox.set_config(&MailboxConfig {
+            capacity: 2,
+            on_overflow: OverflowPolicy::Reject,
+            max_age: None,
+            ..Default::default()
+        });
+        assert!(mailbox.try_send(envelope(2)).is_ok());
+        assert!(mailbox.try_send(envelope(3)).unwrap_err().is_full());
+        assert_eq!(recv_all(&mailbox), vec![1, 2]);
+    }
+
+    #[test]
+    fn high_priority() {
+        #[message(protocol = "test", priority = "high")]
+        struct Urgent(u32);
+
+        let mailbox = mailbox(1, OverflowPolicy::Reject);
+        assert!(mailbox.try_send(envelope(1)).is_ok());
+        assert!(mailbox.try_send(envelope(2)).unwrap_err().is_full());
+
+        // The capacity is applied to high priority messages separately.
+        assert!(mailbox.try_send(envelope_of(Urgent(1))).is_ok());
+        assert!(mailbox
+            .try_send(envelope_of(Urgent(2)))
+            .unwrap_err()
+            .is_full());
+
+        // Control messages are never limited.
+        assert!(mailbox.try_send(envelope_of(Terminate::default())).is_ok());
+        assert!(mailbox.try_send(envelope_of(Terminate::default())).is_ok());
+        assert_eq!(mailbox.len(), 4);
+
+        for _ in 0..2 {
+            let result = mailbox.try_recv();
+            assert!(matches!(result, Some(RecvResult::Data(e)) if e.is::<Terminate>()));
+        }
+        let result = mailbox.try_recv();
+        assert!(matches!(result, Some(RecvResult::Data(e)) if e.is::<Urgent>()));
+        assert_eq!(recv_all(&mailbox), vec![1]);
+        assert_eq!(mailbox.len(), 0);
+    }
+
+    #[test]
+    fn stale() {
+        with_instant_mock(|mock| {
+            let mailbox = Mailbox::new(&MailboxConfig {
+                max_age: Some(Duration::from_secs(2)),
+                ..MailboxConfig::default()
+            });
+
+            assert!(mailbox.try_send(envelope_of(Terminate::default())).is_ok());
+            assert!(mailbox.try_send(envelope(1)).is_ok());
+            mock.advance(Duration::from_secs(1));
+            assert!(mailbox.try_send(envelope(2)).is_ok());
+            mock.advance(Duration::from_millis(1500));
+            assert!(mailbox.try_send(envelope(3)).is_ok());
+
+            // Control messages are never stale.
+            let result = mailbox.try_recv();
+            assert!(matches!(result, Some(RecvResult::Data(e)) if e.is::<Terminate>()));
+            assert!(matches!(mailbox.try_recv(), Some(RecvResult::Stale(_))));
+            assert_eq!(recv_all(&mailbox), vec![2, 3]);
+        });
+    }
+
+    #[test]
+    fn evicted() {
+        let mailbox = mailbox(2, OverflowPolicy::Block);
+        let trace_id = TraceId::try_from(1).unwrap();
+        mailbox.control.lock().idle_since = Some(Instant::now());
+        assert!(matches!(
+            mailbox.close_if_idle(Duration::ZERO, trace_id),
+            IdleCheck::Evicted
+        ));
+
+        // The receiver stops, but envelopes are kept for the next actor.
+        assert!(matches!(mailbox.try_recv(), Some(RecvResult::Closed(_))));
+        assert!(mailbox.try_send(envelope(1)).is_ok());
+        assert!(!mailbox.close(trace_id));
+        mailbox.drop_all();
+
+        let envelopes = mailbox.close_evicted();
+        assert_eq!(envelopes.len(), 1);
+        assert!(mailbox.try_send(envelope(2)).unwrap_err().is_closed());
+    }
+
+    #[tokio::test]
+    async fn blocked_sender() {
+        let mailbox = std::sync::Arc::new(mailbox(1, OverflowPolicy::Block));
+        mailbox.send(envelope(1)).await.unwrap();
+
+        let mailbox1 = mailbox.clone();
+        let sending = tokio::spawn(async move { mailbox1.send(envelope(2)).await.is_ok() });
+        tokio::task::yield_now().await;
+        assert!(!sending.is_finished());
+
+        assert_eq!(recv_all(&mailbox), vec![1]);
+        assert!(sending.await.unwrap());
+        assert_eq!(recv_all(&mailbox), vec![2]);
+
+        // Closing wakes blocked senders up.
+        mailbox.send(envelope(3)).await.unwrap();
+        let mailbox1 = mailbox.clone();
+        let sending = tokio::spawn(async move { mailbox1.send(envelope(4)).await.is_err() });
+        tokio::task::yield_now().await;
+        assert!(mailbox.close(TraceId::try_from(1).unwrap()));
+        assert!(sending.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn blocked_senders_per_queue() {
+        #[message(protocol = "test", priority = "high")]
+        struct Urgent;
+
+        let mailbox = std::sync::Arc::new(mailbox(1, OverflowPolicy::Block));
+        mailbox.send(envelope(1)).await.unwrap();
+        mailbox.send(envelope_of(Urgent)).await.unwrap();
+
+        let mailbox1 = mailbox.clone();
+        let regular = tokio::spawn(async move { mailbox1.send(envelope(2)).await.is_ok() });
+        let mailbox1 = mailbox.clone();
+        let urgent = tokio::spawn(async move { mailbox1.send(envelope_of(Urgent)).await.is_ok() });
+        tokio::task::yield_now().await;
+        assert!(!regular.is_finished());
+        assert!(!urgent.is_finished());
+
+        // Only the sender blocked on the drained queue is woken up.
+        let result = mailbox.try_recv();
+        assert!(matches!(result, Some(RecvResult::Data(e)) if e.is::<Urgent>()));
+        assert!(urgent.await.unwrap());
+        assert!(!regular.is_finished());
+
+        let result = mailbox.try_recv();
+        assert!(matches!(result, Some(RecvResult::Data(e)) if e.is::<Urgent>()));
+        assert_eq!(recv_all(&mailbox), vec![1]);
+        assert!(regular.await.unwrap());
+        assert_eq!(recv_all(&mailbox), vec![2]);
+    }
+}
@@ -38,6 +38,18 @@ pub trait Message: fmt::Debug + Clone + Any + Send + Serialize + for<'de> Deseri
         self._vtable().dumping_allowed
     }
 
+    #[doc(hidden)]
+    #[inline(always)]
+    fn high_priority(&self) -> bool {
+        self._vtable().high_priority
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    fn control(&self) -> bool {
+        self._vtable().control
+    }
+
     #[doc(hidden)]
     #[inline(always)]
     fn upcast(self) -> AnyMessage {
@@ -346,6 +358,11 @@ pub struct MessageVTable {
     pub protocol: &'static str,
     pub labels: &'static [Label],
     pub dumping_allowed: bool, // TODO: introduce `DumpingMode`.
+    /// High priority messages are received before regular ones.
+    pub high_priority: bool,
+    /// Built-in control messages (`Terminate`, `Ping` and so on), which are
+    /// never limited by the mailbox capacity. Implies `high_priority`.
+    pub control: bool,
     pub clone: fn(&AnyMessage) -> AnyMessage,
     pub debug: fn(&AnyMessage, &mut fmt::Formatter<'_>) -> fmt::Result,
     pub erase: fn(&AnyMessage) -> dumping::ErasedMessage,
@@ -382,6 +399,30 @@ fn lookup_vtable(protocol: &str, name: &str) -> Option<&'static MessageVTable> {
     MESSAGES.get(&(protocol, name)).copied()
 }
 
+/// Looks up the vtable of the message type without an instance.
+///
+/// Messages are registered by `(protocol, name)`, where the name is the type's
+/// name and the protocol is the crate's name by default. Thus, the type's path
+/// is used to find the name and to choose among messages with the same name.
+pub(crate) fn lookup_vtable_by_type<M: Message>() -> Option<&'static MessageVTable> {
+    let path = std::any::type_name::<M>();
+    let name = path.rsplit("::").next().unwrap_or(path);
+    let krate = path.split("::").next().unwrap_or(path);
+
+    let mut candidates = MESSAGE_LIST.iter().filter(|vtable| vtable.name == name);
+    let first = candidates.next()?;
+    let rest = candidates.collect::<Vec<_>>();
+
+    if rest.is_empty() {
+        return Some(*first);
+    }
+
+    std::iter::once(first)
+        .chain(rest)
+        .find(|vtable| vtable.protocol.replace('-', "_") == krate)
+        .copied()
+}
+
 pub(crate) fn check_uniqueness() -> Result<(), Vec<(String, String)>> {
     if MESSAGES.len() == MESSAGE_LIST.len() {
         return Ok(());
@@ -461,4 +502,11 @@ mod tests {
             r#"{"protocol":"elfo-core","name":"MyCoolMessage","payload":{"field_a":123,"field_b":"Hello world","field_c":0.5}}"#
         );
     }
+
+    #[test]
+    fn lookup_vtable_by_type() {
+        let vtable = message::lookup_vtable_by_type::<MyCoolMessage>().unwrap();
+        assert_eq!(vtable.protocol, "elfo-core");
+        assert_eq!(vtable.name, "MyCoolMessage");
+    }
 }
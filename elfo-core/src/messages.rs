@@ -4,8 +4,11 @@ use derive_more::Constructor;
 
 use crate::{
     actor::{ActorMeta, ActorStatus},
+    addr::Addr,
     config::AnyConfig,
+    local::Local,
     message,
+    message::AnyMessage,
 };
 
 /// A helper type for using in generic code (e.g. as an associated type) to
@@ -19,19 +22,19 @@ pub enum Impossible {}
 
 /// Checks that the actor is able to handle messages.
 /// Routed to all actors in a group by default and handled implicitly by actors.
-#[message(ret = ())]
+#[message(ret = (), priority = "control")]
 #[derive(Default)]
 #[non_exhaustive]
 pub struct Ping;
 
-#[message(ret = Result<(), ConfigRejected>)]
+#[message(ret = Result<(), ConfigRejected>, priority = "control")]
 #[derive(Constructor)]
 #[non_exhaustive]
 pub struct ValidateConfig {
     pub config: AnyConfig,
 }
 
-#[message(ret = Result<(), ConfigRejected>)]
+#[message(ret = Result<(), ConfigRejected>, priority = "control")]
 #[derive(Constructor)]
 #[non_exhaustive]
 pub struct UpdateConfig {
@@ -80,9 +83,16 @@ pub struct ConfigUpdated {
     // TODO: add `old_config`.
 }
 
+/// Sent to entrypoints once a group is mounted or unmounted while the node is
+/// running. Configurers use it to send configs to new groups.
 #[message]
 #[derive(Default)]
 #[non_exhaustive]
+pub struct TopologyChanged;
+
+#[message(priority = "control")]
+#[derive(Default)]
+#[non_exhaustive]
 pub struct Terminate {
     pub(crate) closing: bool,
 }
@@ -117,3 +127,101 @@ pub struct ActorStatusReport {
     pub meta: Arc<ActorMeta>,
     pub status: ActorStatus,
 }
+
+// === Monitoring ===
+
+/// Asks the recipient actor to send [`ActorDown`] to the sender once it
+/// terminates. Handled implicitly by actors, use [`Context::monitor()`].
+///
+/// [`Context::monitor()`]: crate::Context::monitor
+#[message(priority = "control")]
+#[derive(Default)]
+#[non_exhaustive]
+pub struct Monitor;
+
+/// Cancels a previous [`Monitor`]. Handled implicitly by actors,
+/// use [`Context::demonitor()`].
+///
+/// [`Context::demonitor()`]: crate::Context::demonitor
+#[message(priority = "control")]
+#[derive(Default)]
+#[non_exhaustive]
+pub struct Demonitor;
+
+/// Sent to watchers when a monitored actor terminates or cannot be monitored.
+#[message(priority = "control")]
+#[derive(Constructor)]
+#[non_exhaustive]
+pub struct ActorDown {
+    /// The monitored actor.
+    // Not transferred over the network, restored from the envelope's sender.
+    #[serde(skip, default = "null_addr")]
+    pub addr: Addr,
+    /// The final status, `None` if the actor's fate is unknown.
+    pub status: Option<ActorStatus>,
+    pub reason: ActorDownReason,
+}
+
+fn null_addr() -> Addr {
+    Addr::NULL
+}
+
+#[message(part)]
+#[derive(Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ActorDownReason {
+    /// The actor has terminated or failed, see `status`.
+    Finished,
+    /// The actor doesn't exist or has already finished.
+    NotFound,
+    /// The connection to the actor's node has been lost.
+    Disconnected,
+}
+
+// === Dead letters ===
+
+/// A message that hasn't reached any mailbox.
+///
+/// It's sent to the group marked by [`Local::dead_letters()`] and contains
+/// a copy of the original message, the sender gets an error as usual.
+///
+/// [`Local::dead_letters()`]: crate::topology::Local::dead_letters
+#[message]
+#[non_exhaustive]
+pub struct DeadLetter {
+    pub reason: DeadLetterReason,
+    pub sender: Local<Addr>,
+    /// `Addr::NULL` if the message has been sent using the routing system.
+    pub recipient: Local<Addr>,
+    pub message: AnyMessage,
+}
+
+#[message(part)]
+#[derive(Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeadLetterReason {
+    /// There are no routes for the message or the recipient doesn't exist.
+    Unroutable,
+    /// Mailboxes are closed or there are no relevant actors.
+    Closed,
+    /// Mailboxes are full, only for `try_send*` methods and the `Reject`
+    /// overflow policy.
+    Full,
+    /// Routers have discarded the message.
+    Discarded,
+    /// There are no actors with the `Normal` status among keys of
+    /// `Outcome::HealthyUnicast`.
+    Unhealthy,
+}
+
+impl DeadLetterReason {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unroutable => "Unroutable",
+            Self::Closed => "Closed",
+            Self::Full => "Full",
+            Self::Discarded => "Discarded",
+            Self::Unhealthy => "Unhealthy",
+        }
+    }
+}
@@ -18,6 +18,7 @@ use crate::{
     addr::Addr,
     envelope::Envelope,
     errors::{RequestError, SendError, TrySendError},
+    messages::{ActorStatusReport, DeadLetterReason},
     request_table::ResponseToken,
 };
 
@@ -58,27 +59,36 @@ impl Object {
         self.addr
     }
 
+    #[stability::unstable]
+    pub fn send(
+        this: BorrowedObject<'_>,
+        recipient: Addr,
+        envelope: Envelope,
+    ) -> impl Future<Output = SendResult> + 'static {
+        let fut = Self::deliver(this, recipient, envelope);
+        async move { fut.await.map_err(Undelivered::into_send_error) }
+    }
+
     // Tries to send an envelope to the object synchronously.
     // Only if the object is full, it gets an owned link to the object
     // (because an EBR guard cannot be hold over the async boundary)
     // and sends the envelope asynchronously.
-    #[stability::unstable]
-    pub fn send(
+    pub(crate) fn deliver(
         this: BorrowedObject<'_>,
         recipient: Addr,
         envelope: Envelope,
-    ) -> impl Future<Output = SendResult> + 'static {
+    ) -> impl Future<Output = DeliveryResult> + 'static {
         match &this.kind {
             ObjectKind::Actor(handle) => match handle.try_send(envelope) {
                 Ok(()) => SendFut::Ready(Ok(())),
-                Err(TrySendError::Closed(envelope)) => SendFut::Ready(Err(SendError(envelope))),
                 Err(TrySendError::Full(envelope)) => {
                     let this = this.to_owned();
                     SendFut::WaitActor(async move {
                         let actor = this.as_actor().unwrap();
-                        actor.send(envelope).await
+                        actor.send(envelope).await.map_err(Undelivered::from)
                     })
                 }
+                Err(err) => SendFut::Ready(Err(err.into())),
             },
             ObjectKind::Group(handle) => {
                 let mut visitor = SendGroupVisitor::default();
@@ -88,7 +98,6 @@ impl Object {
             #[cfg(feature = "network")]
             ObjectKind::Remote(handle) => match handle.try_send(recipient, envelope) {
                 Ok(()) => SendFut::Ready(Ok(())),
-                Err(TrySendError::Closed(envelope)) => SendFut::Ready(Err(SendError(envelope))),
                 Err(TrySendError::Full(mut envelope)) => {
                     let this = this.to_owned();
                     SendFut::WaitRemote(async move {
@@ -96,7 +105,7 @@ impl Object {
                         loop {
                             match handle.send(recipient, envelope) {
                                 remote::SendResult::Ok => break Ok(()),
-                                remote::SendResult::Err(err) => break Err(err),
+                                remote::SendResult::Err(err) => break Err(err.into()),
                                 remote::SendResult::Wait(notified, e) => {
                                     envelope = e;
                                     notified.await;
@@ -105,6 +114,7 @@ impl Object {
                         }
                     })
                 }
+                Err(err) => SendFut::Ready(Err(err.into())),
             },
         }
     }
@@ -115,15 +125,21 @@ impl Object {
         recipient: Addr,
         envelope: Envelope,
     ) -> Result<(), TrySendError<Envelope>> {
+        (self.try_deliver(recipient, envelope)).map_err(Undelivered::into_try_send_error)
+    }
+
+    pub(crate) fn try_deliver(&self, recipient: Addr, envelope: Envelope) -> DeliveryResult {
         match &self.kind {
-            ObjectKind::Actor(handle) => handle.try_send(envelope),
+            ObjectKind::Actor(handle) => handle.try_send(envelope).map_err(Undelivered::from),
             ObjectKind::Group(handle) => {
                 let mut visitor = TrySendGroupVisitor::default();
                 handle.handle(envelope, &mut visitor);
                 visitor.finish()
             }
             #[cfg(feature = "network")]
-            ObjectKind::Remote(handle) => handle.try_send(recipient, envelope),
+            ObjectKind::Remote(handle) => {
+                (handle.try_send(recipient, envelope)).map_err(Undelivered::from)
+            }
         }
     }
 
@@ -146,6 +162,10 @@ impl Object {
         handle.handle(envelope, visitor);
     }
 
+    pub(crate) fn is_group(&self) -> bool {
+        matches!(self.kind, ObjectKind::Group(_))
+    }
+
     pub(crate) fn as_actor(&self) -> Option<&Actor> {
         match &self.kind {
             ObjectKind::Actor(handle) => Some(handle),
@@ -162,6 +182,24 @@ impl Object {
         }
     }
 
+    /// Returns the number of actors aborted after the termination deadline,
+    /// always `0` for non-groups.
+    pub(crate) fn aborted(&self) -> usize {
+        match &self.kind {
+            ObjectKind::Group(group) => group.aborted(),
+            _ => 0,
+        }
+    }
+
+    /// Returns statuses of actors that haven't finished yet,
+    /// always empty for non-groups.
+    pub(crate) fn alive_actors(&self) -> Vec<ActorStatusReport> {
+        match &self.kind {
+            ObjectKind::Group(group) => group.alive(),
+            _ => Vec::new(),
+        }
+    }
+
     pub(crate) async fn finished(&self) {
         match &self.kind {
             ObjectKind::Actor(actor) => actor.finished().await,
@@ -172,14 +210,66 @@ impl Object {
     }
 }
 
-// === SendFut ===
+// === Undelivered ===
 
 type SendResult = Result<(), SendError<Envelope>>;
+pub(crate) type DeliveryResult = Result<(), Undelivered>;
+
+/// An envelope that hasn't reached any mailbox.
+/// Unlike `SendError`, it distinguishes messages discarded by routers.
+pub(crate) struct Undelivered {
+    pub(crate) reason: DeadLetterReason,
+    pub(crate) envelope: Envelope,
+}
+
+impl Undelivered {
+    pub(crate) fn new(reason: DeadLetterReason, envelope: Envelope) -> Self {
+        Self { reason, envelope }
+    }
+
+    pub(crate) fn into_send_error(self) -> SendError<Envelope> {
+        match self.reason {
+            DeadLetterReason::Full => SendError::Full(self.envelope),
+            DeadLetterReason::Unhealthy => SendError::Unhealthy(self.envelope),
+            _ => SendError::Closed(self.envelope),
+        }
+    }
+
+    pub(crate) fn into_try_send_error(self) -> TrySendError<Envelope> {
+        match self.reason {
+            DeadLetterReason::Full => TrySendError::Full(self.envelope),
+            DeadLetterReason::Unhealthy => TrySendError::Unhealthy(self.envelope),
+            _ => TrySendError::Closed(self.envelope),
+        }
+    }
+}
+
+impl From<SendError<Envelope>> for Undelivered {
+    fn from(err: SendError<Envelope>) -> Self {
+        match err {
+            SendError::Full(envelope) => Self::new(DeadLetterReason::Full, envelope),
+            SendError::Closed(envelope) => Self::new(DeadLetterReason::Closed, envelope),
+            SendError::Unhealthy(envelope) => Self::new(DeadLetterReason::Unhealthy, envelope),
+        }
+    }
+}
+
+impl From<TrySendError<Envelope>> for Undelivered {
+    fn from(err: TrySendError<Envelope>) -> Self {
+        match err {
+            TrySendError::Full(envelope) => Self::new(DeadLetterReason::Full, envelope),
+            TrySendError::Closed(envelope) => Self::new(DeadLetterReason::Closed, envelope),
+            TrySendError::Unhealthy(envelope) => Self::new(DeadLetterReason::Unhealthy, envelope),
+        }
+    }
+}
+
+// === SendFut ===
 
 #[cfg(not(feature = "network"))]
 #[pin_project(project = SendFutProj)]
 enum SendFut<A, G> {
-    Ready(SendResult),
+    Ready(DeliveryResult),
     WaitActor(#[pin] A),
     WaitGroup(#[pin] G),
 }
@@ -187,10 +277,10 @@ enum SendFut<A, G> {
 #[cfg(not(feature = "network"))]
 impl<A, G> Future for SendFut<A, G>
 where
-    A: Future<Output = SendResult>,
-    G: Future<Output = SendResult>,
+    A: Future<Output = DeliveryResult>,
+    G: Future<Output = DeliveryResult>,
 {
-    type Output = SendResult;
+    type Output = DeliveryResult;
 
     fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
         match self.project() {
@@ -204,7 +294,7 @@ where
 #[cfg(feature = "network")]
 #[pin_project(project = SendFutProj)]
 enum SendFut<A, G, R> {
-    Ready(SendResult),
+    Ready(DeliveryResult),
     WaitActor(#[pin] A),
     WaitGroup(#[pin] G),
     WaitRemote(#[pin] R),
@@ -213,11 +303,11 @@ enum SendFut<A, G, R> {
 #[cfg(feature = "network")]
 impl<A, G, R> Future for SendFut<A, G, R>
 where
-    A: Future<Output = SendResult>,
-    G: Future<Output = SendResult>,
-    R: Future<Output = SendResult>,
+    A: Future<Output = DeliveryResult>,
+    G: Future<Output = DeliveryResult>,
+    R: Future<Output = DeliveryResult>,
 {
-    type Output = SendResult;
+    type Output = DeliveryResult;
 
     fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
         match self.project() {
@@ -232,17 +322,25 @@ where
 pub(crate) trait GroupHandle: Send + Sync + 'static {
     fn handle(&self, envelope: Envelope, visitor: &mut dyn GroupVisitor);
     fn finished(&self) -> BoxFuture<'static, ()>;
+    /// Returns the number of actors aborted after the termination deadline.
+    fn aborted(&self) -> usize;
+    /// Returns statuses of actors that haven't finished yet.
+    fn alive(&self) -> Vec<ActorStatusReport>;
 }
 
 /// The visitor of actors inside a group.
 /// Possible sequences of calls:
 /// * `done()`, if handled by a supervisor
+/// * `discarded()`, if discarded by a router or there is no healthy actor
 /// * `empty()`, if no relevant actors in a group
 /// * `visit_last()`, if only one relevant actor in a group
 /// * `visit()`, `visit()`, .., `visit_last()`
 pub trait GroupVisitor {
     fn done(&mut self);
     fn empty(&mut self, envelope: Envelope);
+    fn discarded(&mut self, _reason: DeadLetterReason, envelope: Envelope) {
+        self.empty(envelope);
+    }
     fn visit(&mut self, object: &OwnedObject, envelope: &Envelope);
     fn visit_last(&mut self, object: &OwnedObject, envelope: Envelope);
 }
@@ -254,6 +352,8 @@ struct SendGroupVisitor {
     extra: Option<Envelope>,
     full: SmallVec<[(OwnedObject, Envelope); 1]>,
     has_ok: bool,
+    has_full: bool,
+    discard_reason: Option<DeadLetterReason>,
 }
 
 impl SendGroupVisitor {
@@ -266,14 +366,13 @@ impl SendGroupVisitor {
             Err(TrySendError::Full(envelope)) => {
                 self.full.push((object.clone(), envelope));
             }
-            Err(TrySendError::Closed(envelope)) => {
-                self.extra = Some(envelope);
-            }
+            // Actors' mailboxes are never unhealthy, only closed.
+            Err(err) => self.extra = Some(err.into_inner()),
         }
     }
 
     #[inline]
-    async fn finish(mut self) -> SendResult {
+    async fn finish(mut self) -> DeliveryResult {
         // Wait until messages reach all full actors.
         #[allow(clippy::comparison_chain)]
         if self.full.len() == 1 {
@@ -282,11 +381,7 @@ impl SendGroupVisitor {
             let actor = object.as_actor().expect("group stores only actors");
             match actor.send(envelope).await {
                 Ok(()) => self.has_ok = true,
-                Err(SendError(envelope)) => {
-                    if !self.has_ok {
-                        self.extra = Some(envelope);
-                    }
-                }
+                Err(err) => self.on_send_error(err),
             }
         } else if self.full.len() > 1 {
             let mut futures = Vec::new();
@@ -304,11 +399,7 @@ impl SendGroupVisitor {
             for result in join_all(futures).await {
                 match result {
                     Ok(()) => self.has_ok = true,
-                    Err(SendError(envelope)) => {
-                        if !self.has_ok {
-                            self.extra = Some(envelope);
-                        }
-                    }
+                    Err(err) => self.on_send_error(err),
                 }
             }
         }
@@ -318,7 +409,20 @@ impl SendGroupVisitor {
         if self.has_ok {
             Ok(())
         } else {
-            Err(SendError(self.extra.take().expect("missing envelope")))
+            let envelope = self.extra.take().expect("missing envelope");
+            Err(Undelivered::new(
+                failure_reason(self.has_full, self.discard_reason),
+                envelope,
+            ))
+        }
+    }
+
+    fn on_send_error(&mut self, err: SendError<Envelope>) {
+        if err.is_full() {
+            self.has_full = true;
+        }
+        if !self.has_ok {
+            self.extra = Some(err.into_inner());
         }
     }
 }
@@ -338,6 +442,11 @@ impl GroupVisitor for SendGroupVisitor {
         self.extra = Some(envelope);
     }
 
+    fn discarded(&mut self, reason: DeadLetterReason, envelope: Envelope) {
+        self.discard_reason = Some(reason);
+        self.empty(envelope);
+    }
+
     fn visit(&mut self, object: &OwnedObject, envelope: &Envelope) {
         let envelope = self.extra.take().unwrap_or_else(|| envelope.duplicate());
         self.try_send(object, envelope);
@@ -355,6 +464,7 @@ struct TrySendGroupVisitor {
     extra: Option<Envelope>,
     has_ok: bool,
     has_full: bool,
+    discard_reason: Option<DeadLetterReason>,
 }
 
 impl TrySendGroupVisitor {
@@ -373,16 +483,15 @@ impl TrySendGroupVisitor {
         }
     }
 
-    fn finish(mut self) -> Result<(), TrySendError<Envelope>> {
+    fn finish(mut self) -> DeliveryResult {
         if self.has_ok {
             Ok(())
         } else {
             let envelope = self.extra.take().expect("missing envelope");
-            Err(if self.has_full {
-                TrySendError::Full(envelope)
-            } else {
-                TrySendError::Closed(envelope)
-            })
+            Err(Undelivered::new(
+                failure_reason(self.has_full, self.discard_reason),
+                envelope,
+            ))
         }
     }
 }
@@ -400,6 +509,11 @@ impl GroupVisitor for TrySendGroupVisitor {
         self.extra = Some(envelope);
     }
 
+    fn discarded(&mut self, reason: DeadLetterReason, envelope: Envelope) {
+        self.discard_reason = Some(reason);
+        self.empty(envelope);
+    }
+
     fn visit(&mut self, object: &OwnedObject, envelope: &Envelope) {
         let envelope = self.extra.take().unwrap_or_else(|| envelope.duplicate());
         self.try_send(object, envelope);
@@ -409,3 +523,11 @@ impl GroupVisitor for TrySendGroupVisitor {
         self.try_send(object, envelope);
     }
 }
+
+fn failure_reason(has_full: bool, discard_reason: Option<DeadLetterReason>) -> DeadLetterReason {
+    if has_full {
+        DeadLetterReason::Full
+    } else {
+        discard_reason.unwrap_or(DeadLetterReason::Closed)
+    }
+}
@@ -1,9 +1,13 @@
-use std::{fmt, marker::PhantomData, sync::Arc};
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::{mpsc, Arc},
+};
 
 use idr_ebr::Guard as EbrGuard;
 use parking_lot::Mutex;
 use slotmap::{new_key_type, Key, SlotMap};
-use smallvec::SmallVec;
+use smallvec::{smallvec, SmallVec};
 use tokio::sync::Notify;
 
 use crate::{
@@ -47,18 +51,28 @@ pub(crate) struct RequestTable {
 
 assert_impl_all!(RequestTable: Sync);
 
-type Responses = SmallVec<[Result<Envelope, RequestError>; 1]>;
+/// A response paired with the responder's address, see `ResponseToken::recipient`.
+pub(crate) type Response = (Addr, Result<Envelope, RequestError>);
+pub(crate) type Responses = SmallVec<[Response; 1]>;
 
 #[derive(Default)]
 struct RequestData {
     remainder: usize,
     responses: Responses,
+    /// Local recipients that have got the request, see `ResponseToken::on_delivered()`.
+    /// Tracked only for `collect_all` requests to attribute timeouts.
+    delivered: Option<mpsc::Receiver<Addr>>,
+    /// Recipients that have responded, including already consumed responses.
+    /// Tracked only for `collect_all` requests to attribute timeouts.
+    responders: SmallVec<[Addr; 1]>,
     collect_all: bool,
+    /// Set if responses are consumed one by one, see `wait_next()`.
+    streaming: bool,
 }
 
 impl RequestData {
     /// Returns `true` if the request is done.
-    fn push(&mut self, response: Result<Envelope, RequestError>) -> bool {
+    fn push(&mut self, response: Response) -> bool {
         // Extra responses (in `any` case).
         if self.remainder == 0 {
             // TODO: move to `ResponseToken` to avoid sending extra responses over network.
@@ -69,6 +83,7 @@ impl RequestData {
         self.remainder -= 1;
 
         if self.collect_all {
+            self.responders.push(response.0);
             self.responses.push(response);
             return self.remainder == 0;
         }
@@ -76,13 +91,13 @@ impl RequestData {
         // `Any` request contains at most one related response.
         debug_assert!(self.responses.len() <= 1);
 
-        let is_ok = response.is_ok();
+        let is_ok = response.1.is_ok();
         if self.responses.is_empty() {
             self.responses.push(response);
         }
         // Priority: `Ok(_)` > `Err(Ignored)` > `Err(Failed)`
-        else if !matches!(&self.responses[0], Err(RequestError::Failed)) {
-            debug_assert!(self.responses[0].is_err());
+        else if !matches!(&self.responses[0].1, Err(RequestError::Failed)) {
+            debug_assert!(self.responses[0].1.is_err());
             self.responses[0] = response;
         }
 
@@ -109,13 +124,31 @@ impl RequestTable {
         trace_id: TraceId,
         collect_all: bool,
     ) -> ResponseToken {
+        let (delivered_tx, delivered_rx) = if collect_all {
+            let (tx, rx) = mpsc::channel();
+            (Some(tx), Some(rx))
+        } else {
+            (None, None)
+        };
+
         let mut requests = self.requests.lock();
         let request_id = requests.insert(RequestData {
             remainder: 1,
             responses: Responses::new(),
+            delivered: delivered_rx,
+            responders: SmallVec::new(),
             collect_all,
+            streaming: false,
         });
-        ResponseToken::new(self.owner, request_id, trace_id, book)
+
+        let data = ResponseTokenData {
+            sender: self.owner,
+            request_id,
+            trace_id,
+            book,
+            delivered: delivered_tx,
+        };
+        ResponseToken::from_data(data)
     }
 
     pub(crate) fn cancel_request(&self, request_id: RequestId) {
@@ -123,6 +156,45 @@ impl RequestTable {
         requests.remove(request_id);
     }
 
+    /// Removes the request, so late responses are discarded, and returns
+    /// already received responses, completed with `Err(Timeout)`.
+    ///
+    /// Timeouts are attributed to recipients that have got the request.
+    /// The rest ones (e.g. remote) are reported with `Addr::NULL`.
+    pub(crate) fn time_out(&self, request_id: RequestId) -> Responses {
+        let mut requests = self.requests.lock();
+        let mut request = requests.remove(request_id).expect("unknown request");
+
+        // The request could be completed right after the deadline.
+        if request.remainder == 0 {
+            return request.responses;
+        }
+
+        if request.collect_all {
+            let mut awaited = (request.delivered.as_ref())
+                .map_or_else(Vec::new, |delivered| delivered.try_iter().collect());
+
+            // Exclude recipients that have already responded.
+            for responder in &request.responders {
+                if let Some(pos) = awaited.iter().position(|addr| addr == responder) {
+                    awaited.swap_remove(pos);
+                }
+            }
+
+            let unknown = request.remainder.saturating_sub(awaited.len());
+            let missing = (awaited.into_iter())
+                .chain((0..unknown).map(|_| Addr::NULL))
+                .take(request.remainder)
+                .map(|addr| (addr, Err(RequestError::Timeout)));
+            request.responses.extend(missing);
+            request.responses
+        } else {
+            // Previously received errors are replaced, because the request
+            // could be successfully handled by remaining recipients.
+            smallvec![(Addr::NULL, Err(RequestError::Timeout))]
+        }
+    }
+
     pub(crate) async fn wait(&self, request_id: RequestId) -> Responses {
         loop {
             let waiting = self.notifier.notified();
@@ -140,6 +212,33 @@ impl RequestTable {
         }
     }
 
+    /// Waits for next responses of the `collect_all` request and takes all
+    /// available ones. Returns `None` and removes the request if all responses
+    /// are consumed.
+    pub(crate) async fn wait_next(&self, request_id: RequestId) -> Option<Responses> {
+        loop {
+            let waiting = self.notifier.notified();
+
+            {
+                let mut requests = self.requests.lock();
+                let request = requests.get_mut(request_id).expect("unknown request");
+                debug_assert!(request.collect_all);
+                request.streaming = true;
+
+                if !request.responses.is_empty() {
+                    break Some(std::mem::take(&mut request.responses));
+                }
+
+                if request.remainder == 0 {
+                    requests.remove(request_id);
+                    break None;
+                }
+            }
+
+            waiting.await;
+        }
+    }
+
     pub(crate) fn resolve(
         &self,
         mut token: ResponseToken,
@@ -147,13 +246,19 @@ impl RequestTable {
     ) {
         // Do nothing for forgotten tokens.
         let data = ward!(token.data.take());
+
+        let responder = match &response {
+            Ok(envelope) => envelope.sender(),
+            Err(_) => token.delivery.recipient(),
+        };
+
         let mut requests = self.requests.lock();
 
         // `None` here means the request was with `collect_all = false` and
         // the response has been recieved already.
         let request = ward!(requests.get_mut(data.request_id));
 
-        if request.push(response) {
+        if request.push((responder, response)) || request.streaming {
             // Actors can perform multiple requests in parallel using different
             // wakers, so we should wake all possible wakers up.
             self.notifier.notify_waiters();
@@ -167,7 +272,7 @@ impl RequestTable {
 pub struct ResponseToken<T = AnyMessage> {
     /// `None` if forgotten.
     data: Option<Arc<ResponseTokenData>>,
-    received: bool,
+    delivery: Delivery,
     marker: PhantomData<T>,
 }
 
@@ -176,23 +281,70 @@ struct ResponseTokenData {
     request_id: RequestId,
     trace_id: TraceId,
     book: AddressBook,
+    /// Reports local recipients of the `collect_all` request to the sender
+    /// without locking its request table.
+    delivered: Option<mpsc::Sender<Addr>>,
+}
+
+/// The local actor that has got the request (`Addr::NULL` until delivered)
+/// and whether the request has been received by it. The recipient is used to
+/// attribute errors, which don't contain the responder's address.
+///
+/// Both are packed into one word to keep `Envelope` compact: the recipient is
+/// always local, so the top bit (a part of the node number) is never set.
+#[derive(Clone, Copy)]
+struct Delivery(u64);
+
+impl Delivery {
+    const RECEIVED: u64 = 1 << 63;
+
+    fn new() -> Self {
+        Self(Addr::NULL.into_bits())
+    }
+
+    fn recipient(self) -> Addr {
+        Addr::from_bits(self.0 & !Self::RECEIVED).unwrap_or(Addr::NULL)
+    }
+
+    fn set_recipient(&mut self, recipient: Addr) {
+        debug_assert!(recipient.is_local());
+        self.0 = recipient.into_bits() | (self.0 & Self::RECEIVED);
+    }
+
+    fn is_received(self) -> bool {
+        self.0 & Self::RECEIVED != 0
+    }
+
+    fn into_received(self) -> Self {
+        Self(self.0 | Self::RECEIVED)
+    }
+
+    /// Duplicates are delivered to other recipients.
+    fn duplicate(self) -> Self {
+        Self(self.0 & Self::RECEIVED)
+    }
 }
 
 impl ResponseToken {
     #[doc(hidden)]
     #[inline]
     pub fn new(sender: Addr, request_id: RequestId, trace_id: TraceId, book: AddressBook) -> Self {
-        debug_assert!(!sender.is_null());
-        debug_assert!(!request_id.is_null());
+        Self::from_data(ResponseTokenData {
+            sender,
+            request_id,
+            trace_id,
+            book,
+            delivered: None,
+        })
+    }
+
+    fn from_data(data: ResponseTokenData) -> Self {
+        debug_assert!(!data.sender.is_null());
+        debug_assert!(!data.request_id.is_null());
 
         Self {
-            data: Some(Arc::new(ResponseTokenData {
-                sender,
-                request_id,
-                trace_id,
-                book,
-            })),
-            received: false,
+            data: Some(Arc::new(data)),
+            delivery: Delivery::new(),
             marker: PhantomData,
         }
     }
@@ -234,7 +386,7 @@ impl ResponseToken {
     pub fn into_received<T>(mut self) -> ResponseToken<T> {
         ResponseToken {
             data: self.data.take(),
-            received: true,
+            delivery: self.delivery.into_received(),
             marker: PhantomData,
         }
     }
@@ -244,7 +396,7 @@ impl ResponseToken {
     pub fn duplicate(&self) -> Self {
         Self {
             data: self.do_duplicate(),
-            received: self.received,
+            delivery: self.delivery.duplicate(),
             marker: PhantomData,
         }
     }
@@ -255,6 +407,22 @@ impl ResponseToken {
         self.data = None;
     }
 
+    /// Called once the request is pushed to the local recipient's mailbox.
+    pub(crate) fn on_delivered(&mut self, recipient: Addr) {
+        // Blocked senders retry after `try_send()`.
+        if self.delivery.recipient() == recipient {
+            return;
+        }
+
+        self.delivery.set_recipient(recipient);
+
+        let data = ward!(self.data.as_ref());
+        if let Some(delivered) = &data.delivered {
+            // Fails only if the request is done, so it's fine to ignore.
+            let _ = delivered.send(recipient);
+        }
+    }
+
     fn do_duplicate(&self) -> Option<Arc<ResponseTokenData>> {
         let data = self.data.as_ref()?;
 
@@ -274,7 +442,7 @@ impl<R> ResponseToken<R> {
     pub(crate) fn forgotten() -> Self {
         Self {
             data: None,
-            received: false,
+            delivery: Delivery::new(),
             marker: PhantomData,
         }
     }
@@ -282,7 +450,7 @@ impl<R> ResponseToken<R> {
     pub(crate) fn into_untyped(mut self) -> ResponseToken {
         ResponseToken {
             data: self.data.take(),
-            received: self.received,
+            delivery: self.delivery,
             marker: PhantomData,
         }
     }
@@ -304,10 +472,10 @@ impl<T> Drop for ResponseToken<T> {
         let object = ward!(book.get(data.sender, &guard));
         let this = ResponseToken {
             data: Some(data),
-            received: self.received,
+            delivery: self.delivery,
             marker: PhantomData,
         };
-        let err = if self.received {
+        let err = if self.delivery.is_received() {
             RequestError::Ignored
         } else {
             RequestError::Failed
@@ -1,12 +1,22 @@
-use std::time::Duration;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash, Hasher},
+    time::Duration,
+};
 
-use crate::RestartParams;
+use crate::{Jitter, RestartParams};
 use elfo_utils::time::Instant;
 
 pub(crate) struct RestartBackoff {
     start_time: Instant,
     restart_count: u64,
     power: u64,
+    /// The previous delay, used by `Jitter::Decorrelated`.
+    prev_delay: Duration,
+    /// Created on the first jittered delay.
+    rng: Option<Rng>,
+    /// Mixed into `jitter_seed`, so actors get different sequences.
+    salt: u64,
 }
 
 impl Default for RestartBackoff {
@@ -15,11 +25,21 @@ impl Default for RestartBackoff {
             start_time: Instant::now(),
             restart_count: 0,
             power: 0,
+            prev_delay: Duration::ZERO,
+            rng: None,
+            salt: 0,
         }
     }
 }
 
 impl RestartBackoff {
+    pub(crate) fn new(key: &impl Hash) -> Self {
+        Self {
+            salt: fxhash::hash64(key),
+            ..Self::default()
+        }
+    }
+
     pub(crate) fn start(&mut self) {
         self.start_time = Instant::now();
     }
@@ -29,6 +49,7 @@ impl RestartBackoff {
         if self.start_time.elapsed() >= params.auto_reset {
             self.restart_count = 1;
             self.power = 0;
+            self.prev_delay = params.min_backoff;
             return Some(Duration::ZERO);
         }
         self.restart_count += 1;
@@ -51,8 +72,59 @@ impl RestartBackoff {
             params.max_backoff
         };
 
+        let delay = match params.jitter {
+            Jitter::None => delay,
+            Jitter::Full => self.random(params, Duration::ZERO, delay),
+            Jitter::Equal => self.random(params, delay / 2, delay),
+            Jitter::Decorrelated => {
+                let upper = self.prev_delay.saturating_mul(3);
+                let delay = self.random(params, params.min_backoff, upper.max(params.min_backoff));
+                delay.min(params.max_backoff)
+            }
+        };
+
+        self.prev_delay = delay;
         Some(delay)
     }
+
+    /// Returns a random duration in `[low, high]`.
+    fn random(&mut self, params: &RestartParams, low: Duration, high: Duration) -> Duration {
+        let seed = params.jitter_seed.map(|seed| seed ^ self.salt);
+        let rng = self
+            .rng
+            .get_or_insert_with(|| Rng::new(seed.unwrap_or_else(random_seed)));
+
+        low + (high - low).mul_f64(rng.next_f64())
+    }
+}
+
+/// SplitMix64, which is good enough for jitter and can be seeded.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn random_seed() -> u64 {
+    // `RandomState` is randomly seeded.
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0xE1F0E1F0E1F0E1F0);
+    hasher.finish()
 }
 
 #[cfg(test)]
@@ -132,4 +204,68 @@ mod tests {
         assert_eq!(backoff.next(&params), Some(params.max_backoff));
         assert_eq!(backoff.next(&params), None);
     }
+
+    #[test]
+    fn jitter() {
+        let min = Duration::from_secs(2);
+        let max = Duration::from_secs(60);
+
+        for jitter in [Jitter::Full, Jitter::Equal, Jitter::Decorrelated] {
+            let params = RestartParams::new(min, max).jitter(jitter).jitter_seed(42);
+            let mut backoff = RestartBackoff::default();
+            let mut prev = min;
+
+            for power in 0..10 {
+                let delay = backoff.next(&params).unwrap();
+                let exp = min.saturating_mul(1 << power).min(max);
+
+                match jitter {
+                    Jitter::Full => assert!(delay <= exp),
+                    Jitter::Equal => assert!(exp / 2 <= delay && delay <= exp),
+                    Jitter::Decorrelated => {
+                        assert!(min <= delay && delay <= (prev * 3).min(max));
+                    }
+                    Jitter::None => unreachable!(),
+                }
+                prev = delay;
+            }
+
+            // The same seed produces the same delays.
+            let mut another = RestartBackoff::default();
+            let mut backoff = RestartBackoff::default();
+            for _ in 0..10 {
+                assert_eq!(another.next(&params), backoff.next(&params));
+            }
+        }
+    }
+
+    #[test]
+    fn jitter_after_reset() {
+        time::with_instant_mock(|mock| {
+            let params = RestartParams::new(Duration::from_secs(5), Duration::from_secs(30))
+                .jitter(Jitter::Full)
+                .jitter_seed(42);
+            let mut backoff = RestartBackoff::default();
+            mock.advance(params.auto_reset);
+
+            // The healthy actor is restarted immediately.
+            assert_eq!(backoff.next(&params), Some(Duration::ZERO));
+        });
+    }
+
+    #[test]
+    fn jitter_seed_per_key() {
+        let params = RestartParams::new(Duration::from_secs(2), Duration::from_secs(60))
+            .jitter(Jitter::Full)
+            .jitter_seed(42);
+        let delays = |key: u32| {
+            let mut backoff = RestartBackoff::new(&key);
+            (0..10)
+                .map(|_| backoff.next(&params).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(delays(1), delays(1));
+        assert_ne!(delays(1), delays(2));
+    }
 }
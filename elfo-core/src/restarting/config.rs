@@ -2,7 +2,10 @@ use std::{num::NonZeroU64, time::Duration};
 
 use serde::Deserialize;
 
-use crate::restarting::restart_policy::{RestartParams, RestartPolicy};
+use crate::restarting::{
+    intensity::{Escalation, RestartIntensity},
+    restart_policy::{Jitter, RestartParams, RestartPolicy},
+};
 
 #[derive(Debug, Clone, Default, Deserialize)]
 pub(crate) struct RestartPolicyConfig(Option<WhenConfig>);
@@ -25,6 +28,7 @@ struct RestartParamsConfig {
     auto_reset: Option<Duration>,
     max_retries: Option<NonZeroU64>,
     factor: Option<f64>,
+    jitter: Option<Jitter>,
 }
 
 impl RestartPolicyConfig {
@@ -37,11 +41,35 @@ impl RestartPolicyConfig {
     }
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct RestartIntensityConfig(Option<IntensityConfig>);
+
+#[derive(Debug, Clone, Deserialize)]
+struct IntensityConfig {
+    max_restarts: u32,
+    #[serde(with = "humantime_serde")]
+    window: Duration,
+    escalation: Option<Escalation>,
+}
+
+impl RestartIntensityConfig {
+    pub(crate) fn make_intensity(&self) -> Option<RestartIntensity> {
+        self.0.as_ref().map(|cfg| {
+            let intensity = RestartIntensity::new(cfg.max_restarts, cfg.window);
+            match cfg.escalation {
+                Some(escalation) => intensity.escalation(escalation),
+                None => intensity,
+            }
+        })
+    }
+}
+
 impl RestartParamsConfig {
     fn make_params(&self) -> RestartParams {
         RestartParams::new(self.min_backoff, self.max_backoff)
             .factor(self.factor)
             .auto_reset(self.auto_reset)
             .max_retries(self.max_retries)
+            .jitter(self.jitter)
     }
 }
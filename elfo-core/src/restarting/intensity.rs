@@ -0,0 +1,116 @@
+use std::{collections::VecDeque, time::Duration};
+
+use serde::Deserialize;
+
+use elfo_utils::time::Instant;
+
+/// Limits the number of restarts across the whole group, like restart
+/// intensity in OTP. Once more than `max_restarts` restarts happen within
+/// `window`, the group is considered failed and [`Escalation`] is applied.
+///
+/// Unlike [`RestartParams::max_retries`], which limits retries of each actor
+/// separately, restarts of all actors in the group are counted together.
+///
+/// [`RestartParams::max_retries`]: crate::RestartParams::max_retries
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestartIntensity {
+    pub(crate) max_restarts: u32,
+    pub(crate) window: Duration,
+    pub(crate) escalation: Escalation,
+}
+
+impl RestartIntensity {
+    /// Allows at most `max_restarts` restarts within `window`.
+    ///
+    /// [`Escalation::StopGroup`] is used by default.
+    pub fn new(max_restarts: u32, window: Duration) -> Self {
+        Self {
+            max_restarts,
+            window,
+            escalation: Escalation::StopGroup,
+        }
+    }
+
+    /// Sets the behaviour once the limit is exceeded.
+    pub fn escalation(self, escalation: Escalation) -> Self {
+        Self { escalation, ..self }
+    }
+}
+
+/// The behaviour once [`RestartIntensity`] is exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub enum Escalation {
+    /// The group stops spawning new actors, all its actors are sent
+    /// `Terminate::closing()`. Other groups continue working.
+    StopGroup,
+    /// The whole node is gracefully terminated.
+    TerminateNode,
+}
+
+/// Tracks restarts of actors in the group.
+#[derive(Default)]
+pub(crate) struct RestartTracker {
+    restarts: VecDeque<Instant>,
+}
+
+impl RestartTracker {
+    /// Registers a new restart.
+    /// Returns `false` if the restart exceeds the intensity.
+    pub(crate) fn on_restart(&mut self, intensity: &RestartIntensity) -> bool {
+        let now = Instant::now();
+
+        while let Some(oldest) = self.restarts.front() {
+            if now.duration_since(*oldest) < intensity.window {
+                break;
+            }
+            self.restarts.pop_front();
+        }
+
+        if self.restarts.len() >= intensity.max_restarts as usize {
+            return false;
+        }
+
+        self.restarts.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use elfo_utils::time;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        time::with_instant_mock(|mock| {
+            let mut tracker = RestartTracker::default();
+            let intensity = RestartIntensity::new(3, Duration::from_secs(10));
+
+            for _ in 0..3 {
+                assert!(tracker.on_restart(&intensity));
+                mock.advance(Duration::from_secs(2));
+            }
+            assert!(!tracker.on_restart(&intensity));
+
+            // The first restart is out of the window.
+            mock.advance(Duration::from_secs(4));
+            assert!(tracker.on_restart(&intensity));
+            assert!(!tracker.on_restart(&intensity));
+
+            // All restarts are out of the window.
+            mock.advance(Duration::from_secs(10));
+            for _ in 0..3 {
+                assert!(tracker.on_restart(&intensity));
+            }
+        });
+    }
+
+    #[test]
+    fn zero() {
+        let mut tracker = RestartTracker::default();
+        let intensity = RestartIntensity::new(0, Duration::from_secs(10));
+        assert!(!tracker.on_restart(&intensity));
+    }
+}
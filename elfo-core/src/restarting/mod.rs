@@ -1,6 +1,16 @@
 mod backoff;
 mod config;
+mod intensity;
 mod restart_policy;
+mod strategy;
 
-pub(crate) use self::{backoff::RestartBackoff, config::RestartPolicyConfig};
-pub use restart_policy::{RestartParams, RestartPolicy};
+pub(crate) use self::{
+    backoff::RestartBackoff,
+    config::{RestartIntensityConfig, RestartPolicyConfig},
+    intensity::RestartTracker,
+};
+pub use self::{
+    intensity::{Escalation, RestartIntensity},
+    restart_policy::{Jitter, RestartParams, RestartPolicy},
+    strategy::RestartStrategy,
+};
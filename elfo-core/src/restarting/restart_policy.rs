@@ -1,5 +1,6 @@
 use std::{num::NonZeroU64, time::Duration};
 
+use serde::Deserialize;
 use tracing::warn;
 
 use crate::ActorStatus;
@@ -67,15 +68,18 @@ pub struct RestartParams {
     pub(crate) auto_reset: Duration,
     pub(crate) max_retries: NonZeroU64,
     pub(crate) factor: f64,
+    pub(crate) jitter: Jitter,
+    pub(crate) jitter_seed: Option<u64>,
 }
 
 impl RestartParams {
     /// Creates a new instance with the specified minimum and maximum backoff
-    /// durations. The default values for `auto_reset`, `max_retries`, and
-    /// `factor` are set as follows:
+    /// durations. The default values for `auto_reset`, `max_retries`,
+    /// `factor` and `jitter` are set as follows:
     /// - `auto_reset = min_backoff`
     /// - `max_retries = NonZeroU64::MAX`
     /// - `factor = 2.0`
+    /// - `jitter = Jitter::None`
     pub fn new(min_backoff: Duration, max_backoff: Duration) -> Self {
         RestartParams {
             min_backoff,
@@ -83,6 +87,8 @@ impl RestartParams {
             auto_reset: min_backoff,
             max_retries: NonZeroU64::MAX,
             factor: 2.0,
+            jitter: Jitter::None,
+            jitter_seed: None,
         }
     }
 
@@ -140,4 +146,52 @@ impl RestartParams {
             ..self
         }
     }
+
+    /// Sets the randomization of backoff durations. It prevents actors failed
+    /// because of the same reason from restarting simultaneously.
+    ///
+    /// If jitter is enabled, the first restart after resetting the backoff
+    /// (see [RestartParams::auto_reset]) isn't immediate, but happens within
+    /// `min_backoff` instead.
+    ///
+    /// `None` does not change the `jitter` setting.
+    ///
+    /// If the function isn't used, `jitter = Jitter::None` is used by default.
+    pub fn jitter(self, jitter: impl Into<Option<Jitter>>) -> Self {
+        Self {
+            jitter: jitter.into().unwrap_or(self.jitter),
+            ..self
+        }
+    }
+
+    /// Sets the seed of the random generator used for jitter, which makes
+    /// backoff durations reproducible. The seed is combined with the actor's
+    /// key, so actors of the group still get different sequences.
+    ///
+    /// If the function isn't used, every actor uses a random seed.
+    pub fn jitter_seed(self, seed: u64) -> Self {
+        Self {
+            jitter_seed: Some(seed),
+            ..self
+        }
+    }
+}
+
+/// The randomization of backoff durations, see [RestartParams::jitter].
+///
+/// Here `backoff` is the duration calculated by the exponential backoff.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Deserialize)]
+#[non_exhaustive]
+pub enum Jitter {
+    /// Durations are used as is.
+    #[default]
+    None,
+    /// A random duration in `[0, backoff]`.
+    Full,
+    /// A random duration in `[backoff / 2, backoff]`.
+    Equal,
+    /// A random duration in `[min_backoff, previous * 3]` limited by
+    /// `max_backoff`, where `previous` is the previous duration.
+    /// `factor` isn't used in this case.
+    Decorrelated,
 }
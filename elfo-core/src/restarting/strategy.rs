@@ -0,0 +1,17 @@
+/// Defines which actors of the group are restarted when one of them fails.
+///
+/// Siblings are sent `Terminate::closing()` and restarted after the same
+/// delay as the failed actor, regardless of their own restart policies.
+/// Actors that aren't going to be restarted don't affect siblings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RestartStrategy {
+    /// Only the failed actor is restarted.
+    #[default]
+    OneForOne,
+    /// All actors of the group are restarted along with the failed one.
+    OneForAll,
+    /// Actors started after the failed one are restarted along with it.
+    /// Actors keep their place in the order across restarts.
+    RestForOne,
+}
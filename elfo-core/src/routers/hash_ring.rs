@@ -0,0 +1,283 @@
+use std::{hash::Hash, marker::PhantomData, sync::Arc};
+
+use arc_swap::ArcSwap;
+
+use super::{Outcome, Router};
+use crate::envelope::Envelope;
+
+const DEFAULT_VIRTUAL_NODES: usize = 128;
+
+/// Maps keys of messages to a config-driven number of shards using consistent
+/// hashing with virtual nodes. Actors are keyed by the shard number in
+/// `0..shards`.
+///
+/// When the number of shards changes, only the minimal set of keys moves
+/// to other shards. Use [`HashRing`] to check which keys have been remapped.
+/// Actors for removed shards are terminated.
+///
+/// # Example
+/// ```ignore
+/// let router = HashRingRouter::new(
+///     |config: &Config| config.shards,
+///     |envelope| {
+///         msg!(match envelope {
+///             OrderPlaced { symbol, .. } => Outcome::Unicast(symbol.clone()),
+///             _ => Outcome::Default,
+///         })
+///     },
+/// );
+/// let ring = router.ring();
+///
+/// ActorGroup::new().config::<Config>().router(router).exec(move |ctx| {
+///     let ring = ring.clone();
+///     async move { /* ... */ }
+/// })
+/// ```
+pub struct HashRingRouter<C, P, R> {
+    config: PhantomData<C>,
+    ring: HashRing,
+    virtual_nodes: usize,
+    prepare: P,
+    route: R,
+}
+
+impl<C, P, R, K> HashRingRouter<C, P, R>
+where
+    C: Send + Sync + 'static,
+    P: Fn(&C) -> usize + Send + Sync + 'static,
+    R: Fn(&Envelope) -> Outcome<K> + Send + Sync + 'static,
+    K: Hash,
+{
+    /// Creates a router. `prepare` returns the number of shards from the
+    /// config, `route` returns keys of messages to be hashed.
+    pub fn new(prepare: P, route: R) -> Self {
+        Self {
+            config: PhantomData,
+            ring: HashRing::default(),
+            virtual_nodes: DEFAULT_VIRTUAL_NODES,
+            prepare,
+            route,
+        }
+    }
+
+    /// Sets the number of virtual nodes per shard.
+    /// More nodes give more even distribution, but slower routing.
+    ///
+    /// `128` by default.
+    pub fn virtual_nodes(mut self, virtual_nodes: usize) -> Self {
+        assert!(virtual_nodes > 0, "at least one virtual node is required");
+        self.virtual_nodes = virtual_nodes;
+        self
+    }
+
+    /// Returns a handle to the ring, which is updated along with the router.
+    pub fn ring(&self) -> HashRing {
+        self.ring.clone()
+    }
+}
+
+impl<C, P, R, K> Router<C> for HashRingRouter<C, P, R>
+where
+    C: Send + Sync + 'static,
+    P: Fn(&C) -> usize + Send + Sync + 'static,
+    R: Fn(&Envelope) -> Outcome<K> + Send + Sync + 'static,
+    K: Hash,
+{
+    type Key = usize;
+
+    fn update(&self, config: &C) {
+        self.update_and_retire(config);
+    }
+
+    fn update_and_retire(&self, config: &C) -> Vec<Self::Key> {
+        let shards = (self.prepare)(config);
+        let state = self.ring.state.load();
+        let prev_shards = state.current.shards;
+
+        if prev_shards == shards {
+            return Vec::new();
+        }
+
+        self.ring.state.store(Arc::new(RingState {
+            current: Arc::new(Ring::new(shards, self.virtual_nodes)),
+            previous: state.current.clone(),
+        }));
+
+        (shards..prev_shards).collect()
+    }
+
+    fn route(&self, envelope: &Envelope) -> Outcome<Self::Key> {
+        let state = self.ring.state.load();
+        let ring = &state.current;
+
+        match (self.route)(envelope) {
+            Outcome::Unicast(key) => ring
+                .shard_of(&key)
+                .map_or(Outcome::Discard, Outcome::Unicast),
+            Outcome::GentleUnicast(key) => ring
+                .shard_of(&key)
+                .map_or(Outcome::Discard, Outcome::GentleUnicast),
+            Outcome::Multicast(keys) => Outcome::Multicast(ring.shards_of(&keys)),
+            Outcome::GentleMulticast(keys) => Outcome::GentleMulticast(ring.shards_of(&keys)),
+            Outcome::HealthyUnicast(keys) => Outcome::HealthyUnicast(ring.ordered_shards_of(&keys)),
+            Outcome::Broadcast => Outcome::Broadcast,
+            Outcome::Discard => Outcome::Discard,
+            Outcome::Default => Outcome::Default,
+        }
+    }
+}
+
+// === HashRing ===
+
+/// A handle to the ring of [`HashRingRouter`].
+#[derive(Clone, Default)]
+pub struct HashRing {
+    state: Arc<ArcSwap<RingState>>,
+}
+
+#[derive(Default)]
+struct RingState {
+    current: Arc<Ring>,
+    /// The ring before the last change of the number of shards.
+    previous: Arc<Ring>,
+}
+
+impl HashRing {
+    /// Returns the current number of shards.
+    pub fn shards(&self) -> usize {
+        self.state.load().current.shards
+    }
+
+    /// Returns the shard for the key, `None` if there are no shards.
+    pub fn shard_of<K: Hash + ?Sized>(&self, key: &K) -> Option<usize> {
+        self.state.load().current.shard_of(key)
+    }
+
+    /// Returns `(old, new)` shards if the key has been moved by the last
+    /// change of the number of shards.
+    ///
+    /// Useful for actors to hand over the state of keys they don't own anymore.
+    pub fn remapped<K: Hash + ?Sized>(&self, key: &K) -> Option<(usize, usize)> {
+        let state = self.state.load();
+        let old = state.previous.shard_of(key)?;
+        let new = state.current.shard_of(key)?;
+        (old != new).then_some((old, new))
+    }
+}
+
+#[derive(Default)]
+struct Ring {
+    shards: usize,
+    /// Virtual nodes as `(point, shard)` sorted by points.
+    points: Vec<(u64, usize)>,
+}
+
+impl Ring {
+    fn new(shards: usize, virtual_nodes: usize) -> Self {
+        let mut points = (0..shards)
+            .flat_map(|shard| {
+                (0..virtual_nodes).map(move |node| (hash(&(shard as u64, node as u64)), shard))
+            })
+            .collect::<Vec<_>>();
+
+        points.sort_unstable();
+        Self { shards, points }
+    }
+
+    fn shard_of<K: Hash + ?Sized>(&self, key: &K) -> Option<usize> {
+        let hash = hash(key);
+        let index = self.points.partition_point(|(point, _)| *point < hash);
+
+        // Wrap around the ring.
+        let (_, shard) = self.points.get(index).or_else(|| self.points.first())?;
+        Some(*shard)
+    }
+
+    fn shards_of<K: Hash>(&self, keys: &[K]) -> Vec<usize> {
+        let mut shards = keys
+            .iter()
+            .filter_map(|key| self.shard_of(key))
+            .collect::<Vec<_>>();
+
+        // Different keys can belong to the same shard.
+        shards.sort_unstable();
+        shards.dedup();
+        shards
+    }
+
+    /// Like `shards_of()`, but keeps the order of keys.
+    fn ordered_shards_of<K: Hash>(&self, keys: &[K]) -> Vec<usize> {
+        let mut shards = Vec::with_capacity(keys.len());
+
+        for shard in keys.iter().filter_map(|key| self.shard_of(key)) {
+            if !shards.contains(&shard) {
+                shards.push(shard);
+            }
+        }
+
+        shards
+    }
+}
+
+/// `FxHash` is stable, but poorly distributed, so the result is mixed.
+fn hash<K: Hash + ?Sized>(key: &K) -> u64 {
+    // The finalizer of MurmurHash3.
+    let mut h = fxhash::hash64(key);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^= h >> 33;
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let ring = Ring::new(0, 10);
+        assert_eq!(ring.shard_of(&42), None);
+    }
+
+    #[test]
+    fn distribution() {
+        let ring = Ring::new(4, DEFAULT_VIRTUAL_NODES);
+        let mut counts = [0; 4];
+
+        for key in 0..10_000 {
+            counts[ring.shard_of(&key).unwrap()] += 1;
+        }
+
+        for count in counts {
+            assert!((1_500..3_500).contains(&count), "{counts:?}");
+        }
+    }
+
+    #[test]
+    fn minimal_remapping() {
+        let old = Ring::new(4, DEFAULT_VIRTUAL_NODES);
+        let new = Ring::new(5, DEFAULT_VIRTUAL_NODES);
+        let mut moved = 0;
+
+        for key in 0..10_000 {
+            let (from, to) = (old.shard_of(&key).unwrap(), new.shard_of(&key).unwrap());
+            if from != to {
+                // Keys can move only to the new shard.
+                assert_eq!(to, 4);
+                moved += 1;
+            }
+        }
+
+        assert!((1_000..3_000).contains(&moved), "{moved}");
+
+        // Removing the shard moves only its keys back.
+        for key in 0..10_000 {
+            let (from, to) = (new.shard_of(&key).unwrap(), old.shard_of(&key).unwrap());
+            if from != 4 {
+                assert_eq!(from, to);
+            }
+        }
+    }
+}
@@ -5,15 +5,48 @@ use std::{
 
 use crate::{envelope::Envelope, msg};
 
-pub use self::map::MapRouter;
+pub use self::{
+    hash_ring::{HashRing, HashRingRouter},
+    map::MapRouter,
+    pool::PoolRouter,
+};
 
+mod hash_ring;
 mod map;
+mod pool;
 
 pub trait Router<C>: Send + Sync + 'static {
     type Key: Clone + Hash + Eq + Display + Send + Sync; // TODO: why is `Sync` required?
 
     fn update(&self, _config: &C) {}
     fn route(&self, envelope: &Envelope) -> Outcome<Self::Key>;
+
+    /// Updates the router and returns keys that are no longer valid.
+    /// Actors for these keys are sent `Terminate` and aren't restarted.
+    /// Calls [`Router::update()`] and retires nothing by default.
+    #[inline]
+    fn update_and_retire(&self, config: &C) -> Vec<Self::Key> {
+        self.update(config);
+        Vec::new()
+    }
+
+    /// Routes non-system messages considering the load of actors.
+    /// Calls [`Router::route()`] by default.
+    #[inline]
+    fn route_with_load(
+        &self,
+        envelope: &Envelope,
+        _load: &dyn MailboxLoad<Self::Key>,
+    ) -> Outcome<Self::Key> {
+        self.route(envelope)
+    }
+}
+
+/// Provides the load of actors in the group, see [`Router::route_with_load()`].
+pub trait MailboxLoad<K> {
+    /// Returns the number of envelopes in the actor's mailbox,
+    /// `None` if there is no actor for the key.
+    fn mailbox_len(&self, key: &K) -> Option<usize>;
 }
 
 /// Specifies which actors will get a message.
@@ -36,6 +69,16 @@ pub enum Outcome<T> {
     /// If there is no active or restarting actors for these keys,
     /// the message will be descarded, no actors are started.
     GentleMulticast(Vec<T>),
+    /// Routes a message to the first actor among the specified keys whose
+    /// status is [`ActorStatusKind::Normal`], so the next keys are fallbacks.
+    /// Actors that are initializing, failed and waiting for restart or
+    /// terminating are skipped.
+    /// If there is no such actor, the message is discarded and the sending
+    /// side gets an error, dead letters have the `Unhealthy` reason.
+    /// No actors are started.
+    ///
+    /// [`ActorStatusKind::Normal`]: crate::ActorStatusKind::Normal
+    HealthyUnicast(Vec<T>),
     /// Routes a message to all active actors.
     Broadcast,
     /// Discards a message.
@@ -53,7 +96,7 @@ assert_eq_size!(Outcome<u64>, [u8; 32]);
 assert_eq_size!(Outcome<u128>, [u8; 32]);
 
 impl<T> Outcome<T> {
-    /// Transforms `Unicast` and `Multicast` variants.
+    /// Transforms `Unicast`, `Multicast` and `HealthyUnicast` variants.
     #[inline]
     pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Outcome<U> {
         match self {
@@ -63,6 +106,9 @@ impl<T> Outcome<T> {
             Outcome::GentleMulticast(list) => {
                 Outcome::GentleMulticast(list.into_iter().map(f).collect())
             }
+            Outcome::HealthyUnicast(list) => {
+                Outcome::HealthyUnicast(list.into_iter().map(f).collect())
+            }
             Outcome::Broadcast => Outcome::Broadcast,
             Outcome::Discard => Outcome::Discard,
             Outcome::Default => Outcome::Default,
@@ -0,0 +1,208 @@
+use std::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use super::{MailboxLoad, Outcome, Router};
+use crate::{envelope::Envelope, messages};
+
+/// Spreads messages across a pool of interchangeable workers keyed by
+/// `0..pool_size`, where the size of the pool is taken from the config.
+///
+/// System messages are routed as usual, `UpdateConfig` starts all workers
+/// of the pool. Other messages are unicasted to one of workers, chosen
+/// either in the round-robin fashion or by the shorter mailbox of two
+/// candidates (the "power of two choices").
+///
+/// If the pool is shrunk, workers out of the pool are terminated.
+///
+/// # Example
+/// ```ignore
+/// ActorGroup::new()
+///     .config::<Config>()
+///     .router(PoolRouter::least_loaded(|config: &Config| config.pool_size))
+///     .exec(worker)
+/// ```
+pub struct PoolRouter<C, P> {
+    config: PhantomData<C>,
+    strategy: Strategy,
+    pool_size: AtomicUsize,
+    cursor: AtomicUsize,
+    prepare: P,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Strategy {
+    RoundRobin,
+    LeastLoaded,
+}
+
+impl<C, P> PoolRouter<C, P>
+where
+    C: Send + Sync + 'static,
+    P: Fn(&C) -> usize + Send + Sync + 'static,
+{
+    /// Creates a router choosing workers one by one.
+    /// `prepare` returns the size of the pool from the config.
+    pub fn round_robin(prepare: P) -> Self {
+        Self::new(Strategy::RoundRobin, prepare)
+    }
+
+    /// Creates a router choosing the worker with fewer envelopes in its
+    /// mailbox among two candidates: the next one in the round-robin order
+    /// and a pseudo-random one. It's cheaper than checking all workers and
+    /// still avoids overloaded ones.
+    /// `prepare` returns the size of the pool from the config.
+    pub fn least_loaded(prepare: P) -> Self {
+        Self::new(Strategy::LeastLoaded, prepare)
+    }
+
+    fn new(strategy: Strategy, prepare: P) -> Self {
+        Self {
+            config: PhantomData,
+            strategy,
+            pool_size: AtomicUsize::new(0),
+            cursor: AtomicUsize::new(0),
+            prepare,
+        }
+    }
+}
+
+impl<C, P> Router<C> for PoolRouter<C, P>
+where
+    C: Send + Sync + 'static,
+    P: Fn(&C) -> usize + Send + Sync + 'static,
+{
+    type Key = usize;
+
+    fn update(&self, config: &C) {
+        self.update_and_retire(config);
+    }
+
+    fn update_and_retire(&self, config: &C) -> Vec<Self::Key> {
+        let pool_size = (self.prepare)(config);
+        let prev_pool_size = self.pool_size.swap(pool_size, Ordering::Relaxed);
+        (pool_size..prev_pool_size).collect()
+    }
+
+    // Only system messages are routed here, others go to `route_with_load()`.
+    fn route(&self, envelope: &Envelope) -> Outcome<Self::Key> {
+        if envelope.is::<messages::UpdateConfig>() {
+            let pool_size = self.pool_size.load(Ordering::Relaxed);
+            Outcome::Multicast((0..pool_size).collect())
+        } else {
+            Outcome::Default
+        }
+    }
+
+    fn route_with_load(
+        &self,
+        _envelope: &Envelope,
+        load: &dyn MailboxLoad<Self::Key>,
+    ) -> Outcome<Self::Key> {
+        let pool_size = self.pool_size.load(Ordering::Relaxed);
+        if pool_size == 0 {
+            return Outcome::Discard;
+        }
+
+        let cursor = self.cursor.fetch_add(1, Ordering::Relaxed);
+        let first = cursor % pool_size;
+
+        if self.strategy == Strategy::RoundRobin || pool_size == 1 {
+            return Outcome::Unicast(first);
+        }
+
+        // Another worker, high bits are used because low ones are poorly mixed.
+        let offset = (fxhash::hash64(&cursor) >> 32) as usize % (pool_size - 1);
+        let second = (first + 1 + offset) % pool_size;
+
+        let len = |key| load.mailbox_len(key).unwrap_or(0);
+        let key = if len(&second) < len(&first) {
+            second
+        } else {
+            first
+        };
+
+        Outcome::Unicast(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fxhash::FxHashMap;
+
+    use super::*;
+    use crate::{envelope::MessageKind, message, tracing::TraceId, Addr};
+
+    #[message]
+    struct Job;
+
+    struct Load(FxHashMap<usize, usize>);
+
+    impl MailboxLoad<usize> for Load {
+        fn mailbox_len(&self, key: &usize) -> Option<usize> {
+            self.0.get(key).copied()
+        }
+    }
+
+    fn job() -> Envelope {
+        let kind = MessageKind::Regular { sender: Addr::NULL };
+        Envelope::with_trace_id(Job, kind, TraceId::try_from(1).unwrap()).upcast()
+    }
+
+    fn unicast(outcome: Outcome<usize>) -> usize {
+        match outcome {
+            Outcome::Unicast(key) => key,
+            _ => panic!("unexpected outcome"),
+        }
+    }
+
+    fn route<P>(router: &PoolRouter<usize, P>, load: &Load) -> usize
+    where
+        P: Fn(&usize) -> usize + Send + Sync + 'static,
+    {
+        unicast(router.route_with_load(&job(), load))
+    }
+
+    #[test]
+    fn round_robin() {
+        let router = PoolRouter::round_robin(|size: &usize| *size);
+        let load = Load(FxHashMap::default());
+        assert!(matches!(
+            router.route_with_load(&job(), &load),
+            Outcome::Discard
+        ));
+        assert!(matches!(router.route(&job()), Outcome::Default));
+
+        router.update(&3);
+        let keys = (0..6).map(|_| route(&router, &load)).collect::<Vec<_>>();
+        assert_eq!(keys, [0, 1, 2, 0, 1, 2]);
+
+        // Shrinking.
+        assert_eq!(router.update_and_retire(&1), [1, 2]);
+        assert_eq!(route(&router, &load), 0);
+        assert!(router.update_and_retire(&2).is_empty());
+    }
+
+    #[test]
+    fn least_loaded() {
+        let router = PoolRouter::least_loaded(|size: &usize| *size);
+        router.update(&3);
+
+        // The most loaded worker always loses to another candidate.
+        let load = Load([(0, 5), (1, 2), (2, 7)].into_iter().collect());
+        let keys = (0..30).map(|_| route(&router, &load)).collect::<Vec<_>>();
+        assert!(!keys.contains(&2));
+        assert!(keys.iter().filter(|k| **k == 1).count() > 15);
+
+        // Workers without actors are considered idle.
+        let load = Load([(0, 5), (1, 2)].into_iter().collect());
+        let keys = (0..30).map(|_| route(&router, &load)).collect::<Vec<_>>();
+        assert!(!keys.contains(&0));
+        assert!(keys.iter().filter(|k| **k == 2).count() > 15);
+
+        // A single worker is chosen without checking the load.
+        router.update(&1);
+        assert_eq!(route(&router, &load), 0);
+    }
+}
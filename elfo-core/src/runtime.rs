@@ -23,6 +23,11 @@ impl RuntimeManager {
         self.dedicated.push((Arc::new(filter), handle));
     }
 
+    /// Returns the index of the first dedicated runtime matching the meta.
+    pub(crate) fn dedicated_index(&self, meta: &ActorMeta) -> Option<usize> {
+        self.dedicated.iter().position(|(f, _)| f(meta))
+    }
+
     pub(crate) fn get(&self, meta: &ActorMeta) -> Handle {
         for (f, h) in &self.dedicated {
             if f(meta) {
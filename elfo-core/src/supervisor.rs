@@ -1,29 +1,51 @@
 use std::{
-    any::Any, future::Future, mem, ops::Deref, panic::AssertUnwindSafe, sync::Arc, time::Duration,
+    any::Any,
+    future::Future,
+    mem,
+    ops::Deref,
+    panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
-use dashmap::DashMap;
-use futures::{future::BoxFuture, FutureExt};
+use dashmap::{mapref::entry::Entry, DashMap, DashSet};
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt,
+};
+use futures_intrusive::sync::ManualResetEvent;
 use fxhash::FxBuildHasher;
-use metrics::{decrement_gauge, increment_gauge};
-use parking_lot::RwLock;
+use metrics::{decrement_gauge, histogram, increment_counter, increment_gauge};
+use parking_lot::{Mutex, RwLock};
+use tokio::time::Instant;
 use tracing::{debug, error, error_span, info, warn, Instrument, Span};
 
 use elfo_utils::CachePadded;
+use idr_ebr::Guard as EbrGuard;
 
-use self::{error_chain::ErrorChain, measure_poll::MeasurePoll};
+use self::{error_chain::ErrorChain, evicted_keys::EvictedKeys, measure_poll::MeasurePoll};
 use crate::{
-    actor::{Actor, ActorMeta, ActorStartInfo, ActorStatus},
+    actor::{Actor, ActorMeta, ActorStartInfo, ActorStatus, PreviousRun},
     config::{AnyConfig, Config, SystemConfig},
     context::Context,
-    envelope::Envelope,
+    dead_letters,
+    envelope::{Envelope, MessageKind},
     exec::{Exec, ExecResult},
-    group::TerminationPolicy,
+    group::{GroupSettings, TerminationPolicy},
+    init::TerminateSystem,
+    mailbox::IdleCheck,
     message::Request,
-    messages, msg,
-    object::{GroupVisitor, Object, OwnedObject},
-    restarting::{RestartBackoff, RestartPolicy},
-    routers::{Outcome, Router},
+    messages::{self, ActorDownReason, ActorStatusReport, DeadLetterReason},
+    msg,
+    object::{GroupVisitor, Object, OwnedObject, Undelivered},
+    restarting::{
+        Escalation, RestartBackoff, RestartIntensity, RestartPolicy, RestartStrategy,
+        RestartTracker,
+    },
+    routers::{MailboxLoad, Outcome, Router},
     runtime::RuntimeManager,
     scope::{self, Scope, ScopeGroupShared},
     subscription::SubscriptionManager,
@@ -32,15 +54,33 @@ use crate::{
 };
 
 mod error_chain;
+mod evicted_keys;
 mod measure_poll;
 
 pub(crate) struct Supervisor<R: Router<C>, C, X> {
     meta: Arc<ActorMeta>,
     restart_policy: RestartPolicy,
+    restart_intensity: Option<RestartIntensity>,
+    restarts: Mutex<RestartTracker>,
+    restart_strategy: RestartStrategy,
+    /// Actors terminated along with a failed sibling and delays of restarting.
+    linked_restarts: DashMap<R::Key, Duration, FxBuildHasher>,
+    /// The order of starting keys, used only by `RestartStrategy::RestForOne`.
+    start_order: DashMap<R::Key, u64, FxBuildHasher>,
+    next_start_order: AtomicU64,
     termination_policy: TerminationPolicy,
+    /// Set once the termination deadline is exceeded.
+    abort: ManualResetEvent,
+    abort_scheduled: AtomicBool,
+    aborted: AtomicUsize,
+    idle_timeout: Option<Duration>,
     span: Span,
     context: Context,
     objects: DashMap<R::Key, OwnedObject, FxBuildHasher>,
+    /// Keys of recently evicted actors, removed once the actor is started again.
+    evicted: Mutex<EvictedKeys<R::Key>>,
+    /// Keys of actors retired by the router, removed once the actor finishes.
+    retired: DashSet<R::Key, FxBuildHasher>,
     router: R,
     exec: X,
     control: CachePadded<RwLock<ControlBlock<C>>>,
@@ -65,7 +105,10 @@ macro_rules! get_or_spawn {
             None => $this
                 .objects
                 .entry(key.clone())
-                .or_try_insert_with(|| $this.spawn(key, $start_info, Default::default()).ok_or(()))
+                .or_try_insert_with(|| {
+                    let backoff = RestartBackoff::new(&key);
+                    $this.spawn(key, $start_info, backoff).ok_or(())
+                })
                 .map(|o| o.downgrade()) // FIXME: take an exclusive lock here.
                 .ok(),
         }
@@ -84,8 +127,7 @@ where
         group: String,
         exec: X,
         router: R,
-        restart_policy: RestartPolicy,
-        termination_policy: TerminationPolicy,
+        settings: GroupSettings,
         rt_manager: RuntimeManager,
     ) -> Self {
         let control = ControlBlock {
@@ -103,9 +145,21 @@ where
                 group,
                 key: String::new(),
             }),
-            restart_policy,
-            termination_policy,
+            restart_policy: settings.restart_policy,
+            restart_intensity: settings.restart_intensity,
+            restarts: Mutex::default(),
+            restart_strategy: settings.restart_strategy,
+            linked_restarts: DashMap::default(),
+            start_order: DashMap::default(),
+            next_start_order: AtomicU64::new(0),
+            termination_policy: settings.termination_policy,
+            abort: ManualResetEvent::new(false),
+            abort_scheduled: AtomicBool::new(false),
+            aborted: AtomicUsize::new(0),
+            idle_timeout: settings.idle_timeout,
             objects: DashMap::default(),
+            evicted: Mutex::default(),
+            retired: DashSet::default(),
             router,
             exec,
             control: CachePadded(RwLock::new(control)),
@@ -206,13 +260,17 @@ where
                     }
                 }
 
+                self.schedule_abort();
+
                 self.router.route(&envelope).or(Outcome::Broadcast)
             }
             messages::Ping => {
                 self.router.route(&envelope).or(Outcome::Broadcast)
             }
             _ => {
-                self.router.route(&envelope).or(Outcome::Discard)
+                self.router
+                    .route_with_load(&envelope, self.as_ref())
+                    .or(Outcome::Discard)
             }
         });
 
@@ -239,12 +297,90 @@ where
                 let iter = list.into_iter().filter_map(|key| self.objects.get(&key));
                 self.visit_multiple(envelope, visitor, iter);
             }
+            Outcome::HealthyUnicast(list) => {
+                let healthy = list
+                    .iter()
+                    .filter_map(|key| self.objects.get(key))
+                    .find(|object| {
+                        let actor = object.as_actor().expect("a supervisor stores only actors");
+                        actor.is_normal()
+                    });
+
+                match healthy {
+                    Some(object) => visitor.visit_last(&object, envelope),
+                    None => visitor.discarded(DeadLetterReason::Unhealthy, envelope),
+                }
+            }
             Outcome::Broadcast => self.visit_multiple(envelope, visitor, self.objects.iter()),
-            Outcome::Discard => visitor.empty(envelope),
+            Outcome::Discard => visitor.discarded(DeadLetterReason::Discarded, envelope),
             Outcome::Default => unreachable!("must be altered earlier"),
         }
     }
 
+    /// Removes the evicted actor. If messages have been sent to it during
+    /// eviction, starts a new actor for the key and passes them to it.
+    fn finish_eviction(self: &Arc<Self>, key: &R::Key, timeout: Duration) -> Option<OwnedObject> {
+        // Like on other exits, the order is forgotten. If the actor is started
+        // again, it's a new start for `RestartStrategy::RestForOne`.
+        self.start_order.remove(key);
+
+        {
+            // Routing is blocked by the entry's lock, so no envelopes are lost.
+            let Entry::Occupied(entry) = self.objects.entry(key.clone()) else {
+                return None;
+            };
+
+            let actor = entry
+                .get()
+                .as_actor()
+                .expect("a supervisor stores only actors");
+
+            if actor.try_close_evicted() {
+                // Must be marked before removing to start the actor properly on a message.
+                self.evicted
+                    .lock()
+                    .insert(key.clone(), Instant::now(), timeout);
+                return Some(entry.remove());
+            }
+        }
+
+        // The evicted mailbox still accepts envelopes until it's replaced.
+        let start_info = ActorStartInfo::on_evicted();
+        let backoff = RestartBackoff::new(key);
+        let object = self.spawn(key.clone(), start_info, backoff);
+
+        // Routing is blocked by the entry's lock again, so envelopes sent during
+        // eviction are passed before new ones.
+        let Entry::Occupied(mut entry) = self.objects.entry(key.clone()) else {
+            return None;
+        };
+
+        let Some(object) = object else {
+            let evicted = entry.remove();
+            let actor = evicted.as_actor().expect("a supervisor stores only actors");
+            drop(actor.close_evicted());
+            return Some(evicted);
+        };
+
+        let evicted = entry.insert(object);
+        let evicted_actor = evicted.as_actor().expect("a supervisor stores only actors");
+        let actor = entry
+            .get()
+            .as_actor()
+            .expect("a supervisor stores only actors");
+        let addr = entry.get().addr();
+
+        for envelope in evicted_actor.close_evicted() {
+            if let Err(err) = actor.try_send(envelope) {
+                let err = Undelivered::from(err);
+                dead_letters::on_undelivered(self.context.book(), err.reason, addr, &err.envelope);
+            }
+        }
+
+        debug!("actor will be started again for messages sent during eviction");
+        Some(evicted)
+    }
+
     fn visit_multiple(
         &self,
         envelope: Envelope,
@@ -278,6 +414,20 @@ where
             return None;
         }
 
+        let start_info = if self.evicted.lock().remove(&key, Instant::now()) {
+            ActorStartInfo::on_evicted()
+        } else {
+            start_info
+        };
+        let next_attempt = start_info.previous.as_ref().map_or(0, |p| p.attempt) + 1;
+
+        if self.restart_strategy == RestartStrategy::RestForOne {
+            // Restarted actors keep their place.
+            self.start_order
+                .entry(key.clone())
+                .or_insert_with(|| self.next_start_order.fetch_add(1, Ordering::Relaxed));
+        }
+
         let group_no = self.context.group().group_no().expect("invalid group addr");
         let entry = self.context.book().vacant_entry(group_no);
         let addr = entry.addr();
@@ -291,6 +441,7 @@ where
         );
 
         let system_config = control.system_config.clone();
+        let idle_timeout = system_config.idle_timeout.or(self.idle_timeout);
 
         let user_config = control
             .user_config
@@ -314,22 +465,57 @@ where
 
             info!(%addr, thread = %thread.name().unwrap_or("?"), "started");
 
-            sv.objects
-                .get(&key)
-                .expect("where is the current actor?")
-                .as_actor()
-                .expect("a supervisor stores only actors")
-                .on_start();
+            // Looked up by the address, because the object can be inserted
+            // into `objects` after spawning (e.g. on restart).
+            let object = sv.context.book().get_owned(addr).expect("just created");
+            let actor = object.as_actor().expect("a supervisor stores only actors");
+            actor.on_start();
 
             // It must be called after `entry.insert()`.
             let ctx = ctx.with_addr(addr).with_start_info(start_info);
             let fut = AssertUnwindSafe(async { sv.exec.exec(ctx).await.unify() }).catch_unwind();
-            let new_status = match fut.await {
-                Ok(Ok(())) => ActorStatus::TERMINATED,
-                Ok(Err(err)) => ActorStatus::FAILED.with_details(ErrorChain(&*err)),
-                Err(panic) => ActorStatus::FAILED.with_details(panic_to_string(panic)),
+
+            let mut is_evicted = false;
+            let watchdog = async {
+                if let Some(timeout) = idle_timeout {
+                    is_evicted = evict_if_idle(object.clone(), timeout).await;
+                }
+                future::pending::<()>().await
             };
 
+            // The result can contain a non-`Send` error, so it must not be kept
+            // across awaits below.
+            let (new_status, is_aborted) = {
+                // `None` if aborted after the termination deadline.
+                let result = tokio::select! {
+                    result = fut => Some(result),
+                    _ = watchdog => unreachable!(),
+                    _ = sv.abort.wait() => None,
+                    _ = actor.aborted() => None,
+                };
+
+                match result {
+                    Some(Ok(Ok(()))) => (ActorStatus::TERMINATED, false),
+                    Some(Ok(Err(err))) => {
+                        (ActorStatus::FAILED.with_details(ErrorChain(&*err)), false)
+                    }
+                    Some(Err(panic)) => (
+                        ActorStatus::FAILED.with_details(panic_to_string(panic)),
+                        false,
+                    ),
+                    None => {
+                        sv.aborted.fetch_add(1, Ordering::Relaxed);
+                        increment_counter!("elfo_aborted_actors_total");
+                        let status =
+                            ActorStatus::ABORTED.with_details("termination deadline exceeded");
+                        (status, true)
+                    }
+                }
+            };
+
+            // Terminated along with a failed sibling, see `RestartStrategy`.
+            let linked_after = sv.linked_restarts.remove(&key).map(|(_, after)| after);
+
             let restart_after = {
                 let object = sv.objects.get(&key).expect("where is the current actor?");
 
@@ -346,21 +532,42 @@ where
                     .unwrap_or(sv.restart_policy.clone());
                 let restart_policy = actor.restart_policy().unwrap_or(default_restart_policy);
 
-                let restarting_allowed = restart_policy.restarting_allowed(&new_status)
+                let is_retired = sv.retired.remove(&key).is_some();
+                let restarting_allowed = (linked_after.is_some()
+                    || restart_policy.restarting_allowed(&new_status))
+                    && !is_evicted
+                    && !is_aborted
+                    && !is_retired
                     && !sv.control.read().stop_spawning;
 
-                actor.set_status(new_status);
+                actor.set_status(new_status.clone());
+                sv.notify_monitors(addr, actor.take_monitors(), &new_status);
 
                 restarting_allowed
                     .then(|| {
-                        restart_policy
-                            .restart_params()
-                            .and_then(|p| backoff.next(&p))
+                        linked_after.or_else(|| {
+                            restart_policy
+                                .restart_params()
+                                .and_then(|p| backoff.next(&p))
+                        })
                     })
                     .flatten()
             };
 
+            // Linked restarts are a part of the sibling's one.
+            let restart_after = if linked_after.is_none() {
+                let restart_after = restart_after.filter(|_| sv.check_restart_intensity());
+                if let Some(after) = restart_after.filter(|_| new_status.is_failed()) {
+                    sv.terminate_siblings(&key, after);
+                }
+                restart_after
+            } else {
+                restart_after
+            };
+
             let _ = if let Some(after) = restart_after {
+                histogram!("elfo_restart_delay_seconds", after.as_secs_f64());
+
                 if after == Duration::ZERO {
                     debug!("actor will be restarted immediately");
                 } else {
@@ -375,13 +582,25 @@ where
                 scope::set_trace_id(TraceId::generate());
 
                 backoff.start();
-                if let Some(object) = sv.spawn(key.clone(), ActorStartInfo::on_restart(), backoff) {
+                let object = if sv.retired.remove(&key).is_none() {
+                    let previous = PreviousRun::new(new_status, next_attempt);
+                    sv.spawn(key.clone(), ActorStartInfo::on_restart(previous), backoff)
+                } else {
+                    // Retired while waiting for restart.
+                    None
+                };
+
+                if let Some(object) = object {
                     sv.objects.insert(key.clone(), object)
                 } else {
+                    sv.start_order.remove(&key);
                     sv.objects.remove(&key).map(|(_, v)| v)
                 }
+            } else if let Some(timeout) = idle_timeout.filter(|_| is_evicted) {
+                sv.finish_eviction(&key, timeout)
             } else {
                 debug!("actor won't be restarted");
+                sv.start_order.remove(&key);
                 sv.objects.remove(&key).map(|(_, v)| v)
             }
             .expect("where is the current actor?");
@@ -400,6 +619,7 @@ where
         let actor = Actor::new(
             meta.clone(),
             addr,
+            &system_config.mailbox,
             self.termination_policy.clone(),
             self.status_subscription.clone(),
         );
@@ -418,6 +638,126 @@ where
         Some(object)
     }
 
+    fn notify_monitors(&self, addr: Addr, watchers: Vec<Addr>, status: &ActorStatus) {
+        let guard = EbrGuard::new();
+
+        for watcher in watchers {
+            let message =
+                messages::ActorDown::new(addr, Some(status.clone()), ActorDownReason::Finished);
+            let kind = MessageKind::Regular { sender: addr };
+            let envelope = Envelope::new(message, kind).upcast();
+
+            // Watchers must not block the supervisor, so lost notifications are possible.
+            let object = ward!(self.context.book().get(watcher, &guard), continue);
+            let _ = object.try_send(watcher, envelope);
+        }
+    }
+
+    /// Terminates siblings of the failed actor according to `RestartStrategy`
+    /// in order to restart them after the same delay.
+    fn terminate_siblings(&self, failed: &R::Key, after: Duration) {
+        let min_order = match self.restart_strategy {
+            RestartStrategy::OneForOne => return,
+            RestartStrategy::OneForAll => None,
+            RestartStrategy::RestForOne => Some(*ward!(self.start_order.get(failed), return)),
+        };
+
+        let mut count = 0;
+        for object in self.objects.iter() {
+            let key = object.key();
+            if key == failed {
+                continue;
+            }
+
+            if let Some(min_order) = min_order {
+                let is_later = self.start_order.get(key).is_some_and(|o| *o > min_order);
+                if !is_later {
+                    continue;
+                }
+            }
+
+            self.linked_restarts.insert(key.clone(), after);
+
+            let kind = MessageKind::Regular {
+                sender: self.context.addr(),
+            };
+            let envelope = Envelope::new(messages::Terminate::closing(), kind).upcast();
+            if object.try_send(object.addr(), envelope).is_ok() {
+                count += 1;
+            } else {
+                // The sibling is already finishing on its own.
+                self.linked_restarts.remove(key);
+            }
+        }
+
+        if count > 0 {
+            info!(
+                strategy = ?self.restart_strategy,
+                count,
+                "terminating siblings to restart them along with the failed actor"
+            );
+        }
+    }
+
+    /// Registers a restart and escalates the failure if the intensity is
+    /// exceeded. Returns `false` if the actor mustn't be restarted.
+    fn check_restart_intensity(&self) -> bool {
+        let config_intensity = self
+            .control
+            .read()
+            .system_config
+            .restart_intensity
+            .make_intensity();
+        let intensity = ward!(
+            config_intensity.or_else(|| self.restart_intensity.clone()),
+            return true
+        );
+
+        if self.restarts.lock().on_restart(&intensity) {
+            return true;
+        }
+
+        // The group can be already stopped by escalation or `Terminate`.
+        if mem::replace(&mut self.control.write().stop_spawning, true) {
+            return false;
+        }
+
+        error!(
+            max_restarts = intensity.max_restarts,
+            window = ?intensity.window,
+            escalation = ?intensity.escalation,
+            "restart intensity is exceeded, the group is failed"
+        );
+        increment_counter!("elfo_escalated_failures_total");
+
+        let details = format!(
+            "more than {} restarts within {:?}",
+            intensity.max_restarts, intensity.window
+        );
+        self.status_subscription.send(ActorStatusReport {
+            meta: self.meta.clone(),
+            status: ActorStatus::FAILED.with_details(details),
+        });
+
+        match intensity.escalation {
+            Escalation::StopGroup => {
+                for object in self.objects.iter() {
+                    let kind = MessageKind::Regular {
+                        sender: self.context.addr(),
+                    };
+                    let envelope = Envelope::new(messages::Terminate::closing(), kind).upcast();
+                    let _ = object.try_send(object.addr(), envelope);
+                }
+            }
+            Escalation::TerminateNode => {
+                let init = ward!(self.context.book().system_init(), return false);
+                let _ = self.context.try_send_to(init, TerminateSystem);
+            }
+        }
+
+        false
+    }
+
     fn spawn_on_group_mounted(self: &Arc<Self>, outcome: Outcome<R::Key>) {
         let start_info = ActorStartInfo::on_group_mounted();
         match outcome {
@@ -431,6 +771,7 @@ where
             }
             Outcome::GentleUnicast(_)
             | Outcome::GentleMulticast(_)
+            | Outcome::HealthyUnicast(_)
             | Outcome::Broadcast
             | Outcome::Discard
             | Outcome::Default => {}
@@ -445,18 +786,64 @@ where
         control.system_config = config.get_system().clone();
         control.user_config = Some(config.get_user::<C>().clone());
 
-        self.router
-            .update(control.user_config.as_ref().expect("just saved"));
+        // Apply limits before `UpdateConfig` is received by actors, because
+        // it can be stuck behind a backlog of regular messages.
+        for object in self.objects.iter() {
+            let actor = object.as_actor().expect("a supervisor stores only actors");
+            actor.set_mailbox_config(&control.system_config.mailbox);
+        }
+
+        let retired = self
+            .router
+            .update_and_retire(control.user_config.as_ref().expect("just saved"));
 
         self.in_scope(|| {
             debug!(
                 message = "config updated",
                 system = ?control.system_config,
                 custom = ?control.user_config.as_ref().unwrap(),
-            )
+            );
+
+            self.terminate_retired(retired);
         });
     }
 
+    fn terminate_retired(&self, keys: Vec<R::Key>) {
+        for key in keys {
+            // Marked before the lookup, so the actor finishing concurrently isn't restarted.
+            self.retired.insert(key.clone());
+
+            let Some(object) = self.objects.get(&key) else {
+                self.retired.remove(&key);
+                continue;
+            };
+
+            info!(key = %key, "actor is retired by the router");
+
+            // `Terminate` is a control message, so it cannot be rejected because of
+            // a full mailbox. A closed mailbox means the actor is already finishing.
+            // The actor applies the termination policy, e.g. closes the mailbox.
+            let kind = MessageKind::Regular {
+                sender: self.context.addr(),
+            };
+            let envelope = Envelope::new(messages::Terminate::default(), kind).upcast();
+            let _ = object.try_send(object.addr(), envelope);
+
+            // Unlike the group's termination, only this actor is aborted.
+            // The object isn't captured to not keep it alive until the deadline.
+            if let Some(deadline) = self.termination_policy.deadline {
+                let book = self.context.book().clone();
+                let addr = object.addr();
+                tokio::spawn(async move {
+                    tokio::time::sleep(deadline).await;
+                    let object = ward!(book.get_owned(addr));
+                    let actor = object.as_actor().expect("a supervisor stores only actors");
+                    actor.abort();
+                });
+            }
+        }
+    }
+
     fn subscribe_to_statuses(&self, addr: Addr, forcing: bool) {
         // Firstly, add the subscriber to handle new objects right way.
         if !self.status_subscription.add(addr) && !forcing {
@@ -482,6 +869,50 @@ where
         }
     }
 
+    /// Returns the number of actors aborted after the termination deadline.
+    pub(crate) fn aborted(&self) -> usize {
+        self.aborted.load(Ordering::Relaxed)
+    }
+
+    fn schedule_abort(self: &Arc<Self>) {
+        let deadline = ward!(self.termination_policy.deadline);
+        if self.abort_scheduled.swap(true, Ordering::Relaxed) {
+            return;
+        }
+
+        let sv = self.clone();
+        let scope = Scope::new(
+            scope::trace_id(),
+            Addr::NULL,
+            self.meta.clone(),
+            self.scope_shared.clone(),
+        );
+
+        tokio::spawn(scope.within(async move {
+            tokio::time::sleep(deadline).await;
+
+            if !sv.objects.is_empty() {
+                sv.in_scope(|| warn!(?deadline, "termination deadline exceeded, aborting actors"));
+            }
+            sv.abort.set();
+        }));
+    }
+
+    /// Returns statuses of actors that haven't finished yet.
+    pub(crate) fn alive(&self) -> Vec<ActorStatusReport> {
+        self.objects
+            .iter()
+            .map(|r| {
+                let actor = r
+                    .value()
+                    .as_actor()
+                    .expect("a supervisor stores only actors");
+                actor.with_status(|report| report)
+            })
+            .filter(|report| !report.status.is_finished())
+            .collect()
+    }
+
     pub(crate) fn finished(self: &Arc<Self>) -> BoxFuture<'static, ()> {
         let sv = self.clone();
         let addrs = self
@@ -502,6 +933,34 @@ where
     }
 }
 
+/// Closes the actor's mailbox once the actor has been waiting for messages
+/// for `timeout`. Returns `false` if the mailbox is closed for another reason.
+async fn evict_if_idle(object: OwnedObject, timeout: Duration) -> bool {
+    let actor = object.as_actor().expect("a supervisor stores only actors");
+
+    loop {
+        match actor.close_if_idle(timeout) {
+            IdleCheck::Evicted => {
+                info!(?timeout, "evicted due to inactivity");
+                increment_counter!("elfo_evicted_actors_total");
+                return true;
+            }
+            IdleCheck::Closed => return false,
+            IdleCheck::Active(next_check) => tokio::time::sleep_until(next_check).await,
+        }
+    }
+}
+
+impl<R, C, X> MailboxLoad<R::Key> for Supervisor<R, C, X>
+where
+    R: Router<C>,
+{
+    fn mailbox_len(&self, key: &R::Key) -> Option<usize> {
+        let object = self.objects.get(key)?;
+        Some(object.as_actor()?.mailbox_len())
+    }
+}
+
 fn extract_response_token<R: Request>(envelope: Envelope) -> ResponseToken<R> {
     msg!(match envelope {
         (R, token) => token,
@@ -0,0 +1,82 @@
+use std::{collections::VecDeque, hash::Hash, time::Duration};
+
+use fxhash::FxHashMap;
+use tokio::time::Instant;
+
+/// Keys of recently evicted actors.
+///
+/// Keys are forgotten once the idle timeout passes after eviction, so only
+/// actors evicted during the last timeout are tracked. They were alive just
+/// before it, thus the size doesn't grow with keys that never return.
+pub(super) struct EvictedKeys<K> {
+    keys: FxHashMap<K, Instant>,
+    /// Keys in order of expiration, may contain already removed ones.
+    queue: VecDeque<(Instant, K)>,
+}
+
+impl<K> Default for EvictedKeys<K> {
+    fn default() -> Self {
+        Self {
+            keys: FxHashMap::default(),
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> EvictedKeys<K> {
+    pub(super) fn insert(&mut self, key: K, now: Instant, timeout: Duration) {
+        self.expire(now);
+
+        let expires_at = now + timeout;
+        self.keys.insert(key.clone(), expires_at);
+        self.queue.push_back((expires_at, key));
+    }
+
+    /// Returns `true` if the key has been evicted recently.
+    pub(super) fn remove(&mut self, key: &K, now: Instant) -> bool {
+        self.expire(now);
+        self.keys.remove(key).is_some()
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while let Some((expires_at, _)) = self.queue.front() {
+            if *expires_at > now {
+                break;
+            }
+
+            let (expires_at, key) = self.queue.pop_front().unwrap();
+
+            // The key can be evicted again after restarting.
+            if self.keys.get(&key) == Some(&expires_at) {
+                self.keys.remove(&key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expiration() {
+        let mut keys = EvictedKeys::default();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let timeout = Duration::from_secs(10);
+
+        keys.insert(1, at(0), timeout);
+        keys.insert(2, at(5), timeout);
+        assert!(keys.remove(&1, at(5)));
+        assert!(!keys.remove(&1, at(5)));
+
+        // Evicted again, the old entry must not remove the new one.
+        keys.insert(1, at(5), timeout);
+        keys.insert(3, at(11), timeout);
+        assert!(keys.remove(&1, at(11)));
+
+        assert!(!keys.remove(&2, at(16)));
+        assert!(keys.remove(&3, at(16)));
+        assert_eq!(keys.queue.len(), 1);
+    }
+}
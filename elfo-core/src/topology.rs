@@ -1,4 +1,4 @@
-use std::{cell::RefCell, sync::Arc};
+use std::sync::Arc;
 
 use parking_lot::RwLock;
 use sealed::sealed;
@@ -7,16 +7,26 @@ use tokio::runtime::Handle;
 #[cfg(feature = "unstable-stuck-detection")]
 use crate::stuck_detection::StuckDetector;
 use crate::{
+    actor::ActorMeta,
     addr::{Addr, GroupNo, NodeLaunchId},
     address_book::{AddressBook, VacantEntry},
     context::Context,
+    contract::Contract,
     demux::Demux,
-    envelope::Envelope,
+    envelope::{Envelope, MessageKind},
     group::Blueprint,
+    messages::TopologyChanged,
     object::Object,
     runtime::RuntimeManager,
+    tracing::TraceId,
 };
 
+pub use self::snapshot::{
+    ConnectionSnapshot, LocalGroupSnapshot, RemoteGroupSnapshot, TopologySnapshot,
+};
+
+mod snapshot;
+
 pub(crate) const SYSTEM_INIT_GROUP_NO: u8 = 1;
 
 /// The topology defines local and remote groups, and routes between them.
@@ -57,6 +67,8 @@ pub struct LocalActorGroup {
     pub name: String,
     pub is_entrypoint: bool,
     pub(crate) stop_order: i8,
+    pub(crate) demux: Demux,
+    pub(crate) contract: Contract,
 }
 
 /// Represents a connection between two groups.
@@ -143,18 +155,75 @@ impl Topology {
         let group_no = GroupNo::new(inner.last_group_no, self.launch_id).expect("invalid group no");
 
         let entry = self.book.vacant_entry(group_no);
+        let demux = Demux::default();
         inner.locals.push(LocalActorGroup {
             addr: entry.addr(),
             name: name.clone(),
             is_entrypoint: false,
             stop_order: 0,
+            demux: demux.clone(),
+            contract: Contract::default(),
         });
 
         Local {
             name,
             topology: self,
             entry,
-            demux: RefCell::new(Demux::default()),
+            demux,
+        }
+    }
+
+    /// Terminates the local group and removes it from the topology.
+    ///
+    /// The group is terminated the same way as on the node's termination,
+    /// according to its [`TerminationPolicy`]. Once it's done, routes to and
+    /// from the group are removed.
+    ///
+    /// Can be used to unmount groups mounted at runtime (see [`Local::mount`])
+    /// as well as ones mounted before the start. Entrypoints (e.g. configurers)
+    /// are notified by [`TopologyChanged`].
+    ///
+    /// Returns `false` if there is no such group.
+    ///
+    /// [`TerminationPolicy`]: crate::TerminationPolicy
+    /// [`TopologyChanged`]: crate::messages::TopologyChanged
+    pub async fn unmount(&self, name: &str) -> bool {
+        let group = ward!(self.locals().find(|group| group.name == name), return false);
+
+        crate::init::terminate_unmounted(self, group.clone()).await;
+
+        {
+            let mut inner = self.inner.write();
+            inner.locals.retain(|local| local.addr != group.addr);
+            for local in &inner.locals {
+                local.demux.remove(group.addr);
+            }
+            inner.connections.retain(|conn| {
+                conn.from != group.addr
+                    && !matches!(conn.to, ConnectionTo::Local(addr) if addr == group.addr)
+            });
+        }
+
+        self.book.remove(group.addr);
+        self.notify_entrypoints();
+        true
+    }
+
+    /// Notifies entrypoints (e.g. configurers) about groups mounted or
+    /// unmounted while the node is running.
+    fn notify_entrypoints(&self) {
+        // Entrypoints will see the actual topology on start.
+        if self.book.system_init().is_none() {
+            return;
+        }
+
+        // Can be called outside the actor system, so no scope is available here.
+        for group in self.locals().filter(|group| group.is_entrypoint) {
+            let object = ward!(self.book.get_owned(group.addr), continue);
+            let kind = MessageKind::Regular { sender: Addr::NULL };
+            let message = TopologyChanged::default();
+            let envelope = Envelope::with_trace_id(message, kind, TraceId::generate());
+            let _ = object.try_send(group.addr, envelope.upcast());
         }
     }
 
@@ -169,6 +238,92 @@ impl Topology {
         let inner = self.inner.read();
         inner.connections.clone().into_iter()
     }
+
+    /// Returns a serializable snapshot of the topology.
+    ///
+    /// The node dumps it at startup if the `ELFO_DUMP_TOPOLOGY` environment
+    /// variable is set, see [`try_start()`].
+    ///
+    /// [`try_start()`]: crate::init::try_start
+    pub fn snapshot(&self) -> TopologySnapshot {
+        let inner = self.inner.read();
+
+        let locals = inner
+            .locals
+            .iter()
+            .map(|group| {
+                let meta = ActorMeta {
+                    group: group.name.clone(),
+                    key: String::new(),
+                };
+
+                LocalGroupSnapshot {
+                    name: group.name.clone(),
+                    is_entrypoint: group.is_entrypoint,
+                    stop_order: group.stop_order,
+                    dedicated_rt: inner.rt_manager.dedicated_index(&meta),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        #[cfg(feature = "network")]
+        let remotes = inner
+            .remotes
+            .iter()
+            .map(|group| {
+                let mut nodes = Vec::new();
+                for nodes_by_local in group.nodes.values() {
+                    nodes.extend(nodes_by_local.load().keys().map(|no| no.into_bits()));
+                }
+                nodes.sort_unstable();
+                nodes.dedup();
+
+                RemoteGroupSnapshot {
+                    name: group.name.clone(),
+                    nodes,
+                }
+            })
+            .collect();
+        #[cfg(not(feature = "network"))]
+        let remotes = Vec::new();
+
+        let connections = inner
+            .connections
+            .iter()
+            .filter_map(|conn| {
+                let name_of = |addr: Addr| {
+                    let group = inner.locals.iter().find(|group| group.addr == addr)?;
+                    Some(group.name.clone())
+                };
+
+                let from = name_of(conn.from)?;
+                let (to, is_remote) = match &conn.to {
+                    ConnectionTo::Local(addr) => (name_of(*addr)?, false),
+                    #[cfg(feature = "network")]
+                    ConnectionTo::Remote(name) => (name.clone(), true),
+                };
+
+                Some(ConnectionSnapshot {
+                    from,
+                    to,
+                    is_remote,
+                })
+            })
+            .collect();
+
+        TopologySnapshot {
+            locals,
+            remotes,
+            connections,
+        }
+    }
+
+    /// Renders the topology in the Graphviz DOT format.
+    ///
+    /// It's a shortcut for `topology.snapshot().to_dot()`.
+    pub fn to_dot(&self) -> String {
+        self.snapshot().to_dot()
+    }
 }
 
 /// Represents a local group's settings.
@@ -177,7 +332,7 @@ pub struct Local<'t> {
     topology: &'t Topology,
     name: String,
     entry: VacantEntry<'t>,
-    demux: RefCell<Demux>,
+    demux: Demux,
 }
 
 impl<'t> Local<'t> {
@@ -197,6 +352,24 @@ impl<'t> Local<'t> {
         self
     }
 
+    /// Mark this group as a receiver of [`DeadLetter`] messages.
+    ///
+    /// Dead letters are produced for messages that haven't reached any
+    /// mailbox, e.g. if there are no routes, routers discard messages or
+    /// mailboxes are closed. Without such group they're only counted.
+    ///
+    /// # Panics
+    /// If another group is already marked.
+    ///
+    /// [`DeadLetter`]: crate::messages::DeadLetter
+    #[track_caller]
+    pub fn dead_letters(self) -> Self {
+        if !self.topology.book.set_dead_letters(self.entry.addr()) {
+            panic!("another group already receives dead letters");
+        }
+        self
+    }
+
     /// Defines a route to the given destination (local or remote group).
     ///
     /// # Examples
@@ -222,7 +395,7 @@ impl<'t> Local<'t> {
     pub fn route_to<F>(&self, dest: &impl Destination<F>, filter: F) {
         dest.extend_demux(
             self.entry.addr().group_no().expect("invalid addr"),
-            &mut self.demux.borrow_mut(),
+            &self.demux,
             filter,
         );
 
@@ -233,24 +406,59 @@ impl<'t> Local<'t> {
         });
     }
 
+    /// Defines a route from the already declared local group to this one.
+    ///
+    /// Unlike [`Local::route_to`], the source group can be already mounted,
+    /// so it's useful to connect groups mounted at runtime to existing ones.
+    /// Existing groups can be found by [`Topology::locals`].
+    pub fn route_from<F>(&self, source: &LocalActorGroup, filter: F)
+    where
+        F: Fn(&Envelope) -> bool + Send + Sync + 'static,
+    {
+        let source_group_no = source.addr.group_no().expect("invalid addr");
+        self.extend_demux(source_group_no, &source.demux, filter);
+
+        let mut inner = self.topology.inner.write();
+        inner.connections.push(Connection {
+            from: source.addr,
+            to: <Self as Destination<F>>::connection_endpoint(self),
+        });
+    }
+
     // TODO: deprecate?
     pub fn route_all_to(&self, dest: &Local<'_>) {
         let addr = dest.entry.addr();
-        self.demux
-            .borrow_mut()
-            .append(move |_, addrs| addrs.push(addr));
+        self.demux.append(addr, move |_, addrs| addrs.push(addr));
+
+        let mut inner = self.topology.inner.write();
+        inner.connections.push(Connection {
+            from: self.entry.addr(),
+            to: ConnectionTo::Local(addr),
+        });
     }
 
     /// Mounts a blueprint to this group.
+    ///
+    /// Groups can be mounted at runtime as well. In this case, entrypoints
+    /// (e.g. configurers) are notified by [`TopologyChanged`] in order to
+    /// send a config to the new group, actors aren't started before that.
+    ///
+    /// [`TopologyChanged`]: crate::messages::TopologyChanged
     pub fn mount(self, blueprint: Blueprint) {
-        self.with_group_mut(|group| group.stop_order = blueprint.stop_order);
+        self.with_group_mut(|group| {
+            group.stop_order = blueprint.stop_order;
+            group.contract = blueprint.contract.clone();
+        });
 
+        let topology = self.topology;
         let addr = self.entry.addr();
-        let book = self.topology.book.clone();
-        let ctx = Context::new(book, self.demux.into_inner()).with_group(addr);
-        let rt_manager = self.topology.inner.read().rt_manager.clone();
+        let book = topology.book.clone();
+        let ctx = Context::new(book, self.demux).with_group(addr);
+        let rt_manager = topology.inner.read().rt_manager.clone();
         let object = (blueprint.mount)(ctx, self.name, rt_manager);
         self.entry.insert(object);
+
+        topology.notify_entrypoints();
     }
 
     fn with_group_mut(&self, f: impl FnOnce(&mut LocalActorGroup)) {
@@ -267,20 +475,39 @@ impl<'t> Local<'t> {
 #[sealed]
 pub trait Destination<F> {
     #[doc(hidden)]
-    fn extend_demux(&self, source_group_no: GroupNo, demux: &mut Demux, filter: F);
+    fn extend_demux(&self, source_group_no: GroupNo, demux: &Demux, filter: F);
 
     #[doc(hidden)]
     fn connection_endpoint(&self) -> ConnectionTo;
 }
 
+#[sealed]
+impl<F> Destination<F> for LocalActorGroup
+where
+    F: Fn(&Envelope) -> bool + Send + Sync + 'static,
+{
+    fn extend_demux(&self, _: GroupNo, demux: &Demux, filter: F) {
+        let addr = self.addr;
+        demux.append(addr, move |envelope, addrs| {
+            if filter(envelope) {
+                addrs.push(addr);
+            }
+        });
+    }
+
+    fn connection_endpoint(&self) -> ConnectionTo {
+        ConnectionTo::Local(self.addr)
+    }
+}
+
 #[sealed]
 impl<F> Destination<F> for Local<'_>
 where
     F: Fn(&Envelope) -> bool + Send + Sync + 'static,
 {
-    fn extend_demux(&self, _: GroupNo, demux: &mut Demux, filter: F) {
+    fn extend_demux(&self, _: GroupNo, demux: &Demux, filter: F) {
         let addr = self.entry.addr();
-        demux.append(move |envelope, addrs| {
+        demux.append(addr, move |envelope, addrs| {
             if filter(envelope) {
                 addrs.push(addr);
             }
@@ -410,7 +637,7 @@ cfg_network!({
     where
         F: Fn(&Envelope, &NodeDiscovery) -> Outcome + Send + Sync + 'static,
     {
-        fn extend_demux(&self, local_group_no: GroupNo, demux: &mut Demux, filter: F) {
+        fn extend_demux(&self, local_group_no: GroupNo, demux: &Demux, filter: F) {
             let nodes = self
                 .topology
                 .inner
@@ -424,7 +651,7 @@ cfg_network!({
                 .or_default()
                 .clone();
 
-            demux.append(move |envelope, addrs| {
+            demux.append(Addr::NULL, move |envelope, addrs| {
                 let discovery = NodeDiscovery(());
 
                 match filter(envelope, &discovery) {
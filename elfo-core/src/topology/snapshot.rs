@@ -0,0 +1,243 @@
+use std::fmt::{self, Write as _};
+
+use serde::Serialize;
+
+/// A serializable snapshot of the topology, see [`Topology::snapshot()`].
+///
+/// [`Topology::snapshot()`]: super::Topology::snapshot
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct TopologySnapshot {
+    pub locals: Vec<LocalGroupSnapshot>,
+    /// Always empty without the `network` feature.
+    pub remotes: Vec<RemoteGroupSnapshot>,
+    pub connections: Vec<ConnectionSnapshot>,
+}
+
+/// Represents a local group in [`TopologySnapshot`].
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct LocalGroupSnapshot {
+    pub name: String,
+    pub is_entrypoint: bool,
+    pub stop_order: i8,
+    /// The index of the dedicated runtime in order of
+    /// [`Topology::add_dedicated_rt()`] calls, `None` for the default one.
+    ///
+    /// Filters are called with an empty key, so runtimes chosen by actor
+    /// keys aren't detected.
+    ///
+    /// [`Topology::add_dedicated_rt()`]: super::Topology::add_dedicated_rt
+    pub dedicated_rt: Option<usize>,
+}
+
+/// Represents a remote group in [`TopologySnapshot`].
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct RemoteGroupSnapshot {
+    pub name: String,
+    /// Numbers of nodes registered by `Topology::register_remote()`.
+    pub nodes: Vec<u16>,
+}
+
+/// Represents a route in [`TopologySnapshot`].
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct ConnectionSnapshot {
+    /// The name of the local group.
+    pub from: String,
+    /// The name of the local or remote group.
+    pub to: String,
+    pub is_remote: bool,
+}
+
+impl TopologySnapshot {
+    /// Renders the snapshot in the Graphviz DOT format.
+    ///
+    /// Entrypoints are drawn in bold, remote groups are dashed.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        self.write_dot(&mut out)
+            .expect("writing to a string cannot fail");
+        out
+    }
+
+    fn write_dot(&self, out: &mut String) -> fmt::Result {
+        writeln!(out, "digraph topology {{")?;
+        writeln!(out, "    rankdir=LR;")?;
+        writeln!(out, "    node [shape=box];")?;
+
+        for (no, local) in self.locals.iter().enumerate() {
+            let mut label = local.name.clone();
+            if local.stop_order != 0 {
+                write!(label, "\nstop_order: {}", local.stop_order)?;
+            }
+            if let Some(rt) = local.dedicated_rt {
+                write!(label, "\nruntime: #{rt}")?;
+            }
+
+            let style = if local.is_entrypoint {
+                ", style=bold"
+            } else {
+                ""
+            };
+            writeln!(out, "    l{no} [label={}{style}];", Quoted(&label))?;
+        }
+
+        for (no, remote) in self.remotes.iter().enumerate() {
+            let mut label = remote.name.clone();
+            if !remote.nodes.is_empty() {
+                let nodes = remote.nodes.iter().map(|n| n.to_string());
+                write!(label, "\nnodes: {}", nodes.collect::<Vec<_>>().join(", "))?;
+            }
+
+            writeln!(out, "    r{no} [label={}, style=dashed];", Quoted(&label))?;
+        }
+
+        for conn in &self.connections {
+            let from = ward!(self.local_id(&conn.from), continue);
+            let to = if conn.is_remote {
+                ward!(self.remote_id(&conn.to), continue)
+            } else {
+                ward!(self.local_id(&conn.to), continue)
+            };
+
+            writeln!(out, "    {from} -> {to};")?;
+        }
+
+        writeln!(out, "}}")
+    }
+
+    fn local_id(&self, name: &str) -> Option<String> {
+        let no = self.locals.iter().position(|l| l.name == name)?;
+        Some(format!("l{no}"))
+    }
+
+    fn remote_id(&self, name: &str) -> Option<String> {
+        let no = self.remotes.iter().position(|r| r.name == name)?;
+        Some(format!("r{no}"))
+    }
+}
+
+/// Quotes and escapes a string to be used as a DOT identifier.
+struct Quoted<'a>(&'a str);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                c => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{topology::Topology, ActorMeta};
+
+    fn local(name: &str, is_entrypoint: bool) -> LocalGroupSnapshot {
+        LocalGroupSnapshot {
+            name: name.into(),
+            is_entrypoint,
+            stop_order: 0,
+            dedicated_rt: None,
+        }
+    }
+
+    #[test]
+    fn to_dot() {
+        let snapshot = TopologySnapshot {
+            locals: vec![
+                local("system.configurers", true),
+                LocalGroupSnapshot {
+                    stop_order: 10,
+                    dedicated_rt: Some(0),
+                    ..local("producers", false)
+                },
+                local("consumers \"main\"", false),
+            ],
+            remotes: vec![RemoteGroupSnapshot {
+                name: "gateways".into(),
+                nodes: Vec::new(),
+            }],
+            connections: vec![
+                ConnectionSnapshot {
+                    from: "producers".into(),
+                    to: "consumers \"main\"".into(),
+                    is_remote: false,
+                },
+                ConnectionSnapshot {
+                    from: "producers".into(),
+                    to: "gateways".into(),
+                    is_remote: true,
+                },
+            ],
+        };
+
+        assert_eq!(
+            snapshot.to_dot(),
+            r#"digraph topology {
+    rankdir=LR;
+    node [shape=box];
+    l0 [label="system.configurers", style=bold];
+    l1 [label="producers\nstop_order: 10\nruntime: #0"];
+    l2 [label="consumers \"main\""];
+    r0 [label="gateways", style=dashed];
+    l1 -> l2;
+    l1 -> r0;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn snapshot() {
+        let topology = Topology::empty();
+        let configurers = topology.local("system.configurers").entrypoint();
+        let producers = topology.local("producers");
+        let consumers = topology.local("consumers");
+        configurers.route_to(&producers, |_| true);
+        producers.route_all_to(&consumers);
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let filter = |meta: &ActorMeta| meta.group == "consumers";
+        topology.add_dedicated_rt(filter, rt.handle().clone());
+
+        let snapshot = topology.snapshot();
+        let locals = snapshot
+            .locals
+            .iter()
+            .map(|l| (l.name.as_str(), l.is_entrypoint, l.dedicated_rt))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            locals,
+            [
+                ("system.configurers", true, None),
+                ("producers", false, None),
+                ("consumers", false, Some(0)),
+            ]
+        );
+        assert!(snapshot.remotes.is_empty());
+
+        let connections = snapshot
+            .connections
+            .iter()
+            .map(|c| (c.from.as_str(), c.to.as_str(), c.is_remote))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            connections,
+            [
+                ("system.configurers", "producers", false),
+                ("producers", "consumers", false),
+            ]
+        );
+    }
+}
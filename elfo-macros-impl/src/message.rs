@@ -18,6 +18,8 @@ struct MessageArgs {
     part: bool,
     transparent: bool,
     dumping_allowed: Option<bool>,
+    high_priority: Option<bool>,
+    control: bool,
     crate_: Option<Path>,
     not: Vec<String>,
 }
@@ -31,6 +33,8 @@ impl Parse for MessageArgs {
             part: false,
             transparent: false,
             dumping_allowed: None,
+            high_priority: None,
+            control: false,
             crate_: None,
             not: Vec::new(),
         };
@@ -44,6 +48,7 @@ impl Parse for MessageArgs {
         // `#[message(elfo = some)]`
         // `#[message(not(Debug))]`
         // `#[message(dumping = "disabled")]`
+        // `#[message(priority = "high")]`
         while !input.is_empty() {
             let ident: Ident = input.parse()?;
 
@@ -73,6 +78,21 @@ impl Parse for MessageArgs {
                         return Err(input.error("only `dumping = \"disabled\"` is supported"));
                     }
                 }
+                "priority" => {
+                    let _: Token![=] = input.parse()?;
+                    let s: LitStr = input.parse()?;
+
+                    match s.value().as_str() {
+                        "high" => args.high_priority = Some(true),
+                        "normal" => args.high_priority = Some(false),
+                        // Only for built-in messages, so it's not documented.
+                        "control" => {
+                            args.high_priority = Some(true);
+                            args.control = true;
+                        }
+                        _ => return Err(input.error("priority must be `high` or `normal`")),
+                    }
+                }
                 // TODO: call it `crate` like in linkme?
                 "elfo" => {
                     let _: Token![=] = input.parse()?;
@@ -112,6 +132,7 @@ impl MessageArgs {
             incompatible(&self.name, "name");
             incompatible(&self.protocol, "protocol");
             incompatible(&self.dumping_allowed, "dumping_allowed");
+            incompatible(&self.high_priority, "priority");
         }
     }
 }
@@ -195,6 +216,8 @@ pub fn message_impl(
 
     // TODO: pass to `_elfo_Wrapper`.
     let dumping_allowed = args.dumping_allowed.unwrap_or(true);
+    let high_priority = args.high_priority.unwrap_or(false);
+    let control = args.control;
 
     let network_fns = cfg!(feature = "network").then(|| {
         quote! {
@@ -268,6 +291,8 @@ pub fn message_impl(
                     #internal::metrics::Label::from_static_parts("protocol", #protocol),
                 ],
                 dumping_allowed: #dumping_allowed,
+                high_priority: #high_priority,
+                control: #control,
                 clone,
                 debug,
                 erase,
@@ -21,6 +21,9 @@ pub fn msg_core(input: TokenStream) -> TokenStream {
 /// * `name = "SomeName"` — override a message name.
 /// * `not(Debug)` — do not derive `Debug`. Useful for custom instances.
 /// * `not(Clone)` — the same for `Clone`.
+/// * `priority = "high"` — put the message to the high priority queue of the
+///   mailbox, such messages are received before regular ones. The capacity is
+///   applied to the queue separately.
 /// * `elfo = some::path` — override a path to elfo.
 #[proc_macro_attribute]
 pub fn message(attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -92,7 +92,9 @@ fn do_encode(
             *is_last,
             match &message {
                 Ok(_) => KIND_RESPONSE_OK,
-                Err(RequestError::Failed) => KIND_RESPONSE_FAILED,
+                // Timeouts are produced only by the requester, so they aren't
+                // expected here, but treated as failures just in case.
+                Err(RequestError::Failed | RequestError::Timeout) => KIND_RESPONSE_FAILED,
                 Err(RequestError::Ignored) => KIND_RESPONSE_IGNORED,
             },
             Some(*request_id),
@@ -6,20 +6,21 @@ use parking_lot::Mutex;
 use tracing::{debug, error, info, trace, warn};
 
 use elfo_core::{
-    message, Local, Message,
     _priv::{EbrGuard, EnvelopeOwned, GroupVisitor, MessageKind, NodeNo, Object, OwnedObject},
     errors::{RequestError, SendError, TrySendError},
-    messages::{ConfigUpdated, Impossible},
+    message,
+    messages::{ActorDown, ActorDownReason, ConfigUpdated, Impossible, Monitor},
     msg, remote, scope,
     stream::Stream,
     time::Interval,
-    Addr, Context, Envelope, ResponseToken, Topology,
+    Addr, Context, Envelope, Local, Message, ResponseToken, Topology,
 };
 use elfo_utils::{likely, time::Instant, unlikely};
 
 use self::{
     flows_rx::RxFlows,
     flows_tx::{Acquire, TryAcquire, TxFlows},
+    monitors::{Change as MonitorsChange, RemoteMonitors},
     requests::OutgoingRequests,
 };
 
@@ -42,6 +43,7 @@ use crate::{
 mod flow_control;
 mod flows_rx;
 mod flows_tx;
+mod monitors;
 mod requests;
 
 // TODO: send `CloseFlow` once an actor is closed, not only on incoming message.
@@ -65,10 +67,13 @@ pub(crate) struct Worker {
     local: GroupInfo,
     remote: GroupInfo,
     transport: Option<Transport>,
+    monitors: Arc<Mutex<RemoteMonitors>>,
 }
 
 impl Drop for Worker {
     fn drop(&mut self) {
+        self.notify_monitors();
+
         if let Some(transport) = self.transport.take() {
             let _ = self.ctx.try_send_to(
                 self.ctx.group(),
@@ -97,6 +102,21 @@ impl Worker {
             local,
             remote,
             transport: None,
+            monitors: Default::default(),
+        }
+    }
+
+    /// Notifies local actors monitoring remote ones that the connection is lost.
+    fn notify_monitors(&self) {
+        let guard = EbrGuard::new();
+
+        for (watcher, monitored) in self.monitors.lock().drain() {
+            let message = ActorDown::new(monitored, None, ActorDownReason::Disconnected);
+            let kind = MessageKind::Regular { sender: monitored };
+            let envelope = Envelope::new(message.upcast(), kind);
+
+            let object = ward!(self.ctx.book().get(watcher, &guard), continue);
+            let _ = object.try_send(watcher, envelope);
         }
     }
 
@@ -123,6 +143,7 @@ impl Worker {
         let remote_handle = RemoteHandle {
             tx: local_tx.clone(),
             tx_flows: tx_flows.clone(),
+            monitors: self.monitors.clone(),
         };
         let remote_group_guard = self.topology.register_remote(
             self.ctx.addr(),
@@ -160,6 +181,7 @@ impl Worker {
             tx_flows: tx_flows.clone(),
             rx_flows: rx_flows.clone(),
             requests,
+            monitors: self.monitors.clone(),
         };
         self.ctx.attach(Stream::once(sr.exec()));
 
@@ -375,6 +397,7 @@ struct SocketReader {
     tx_flows: Arc<TxFlows>,
     rx_flows: Arc<Mutex<RxFlows>>,
     requests: Arc<Mutex<OutgoingRequests>>,
+    monitors: Arc<Mutex<RemoteMonitors>>,
 }
 
 impl SocketReader {
@@ -407,6 +430,12 @@ impl SocketReader {
             // Recipients can respond to the sender, so we should add a flow.
             self.tx_flows.add_flow_if_needed(sender);
 
+            // The remote actor has gone, so the connection loss isn't interesting anymore.
+            if unlikely(envelope.is::<ActorDown>()) && recipient != NetworkAddr::NULL {
+                let change = MonitorsChange::Remove(recipient.into_local(), sender.into_remote());
+                self.monitors.lock().apply(change);
+            }
+
             // `NULL` means we should route to the group.
             if recipient == NetworkAddr::NULL {
                 self.handle_routed_message(envelope);
@@ -598,6 +627,7 @@ impl SocketReader {
             let (close, update) = flows.close(recipient);
             self.send_back(close);
             self.send_back(update);
+            self.reject_monitor(recipient, &envelope);
             return;
         };
 
@@ -675,10 +705,11 @@ impl SocketReader {
         let result = object.try_send(Addr::NULL, envelope);
 
         // If the recipient has gone, close the flow and return.
-        if matches!(result, Err(TrySendError::Closed(_))) {
+        if let Err(TrySendError::Closed(envelope)) = &result {
             let (close, update) = flows.close(object.addr());
             self.send_back(close);
             self.send_back(update);
+            self.reject_monitor(object.addr(), envelope);
 
             if routed {
                 self.send_back(flows.release_routed());
@@ -691,7 +722,8 @@ impl SocketReader {
         flow.acquire_direct(!routed);
 
         match result {
-            Ok(()) => {
+            // There are no healthy actors in the group, so the envelope is dropped.
+            Ok(()) | Err(TrySendError::Unhealthy(_)) => {
                 self.send_back(flow.release_direct());
 
                 if routed {
@@ -718,6 +750,24 @@ impl SocketReader {
                 .unwrap();
         }
     }
+
+    /// Replies with `ActorDown` if the remote actor tries to monitor a gone one.
+    fn reject_monitor(&self, recipient: Addr, envelope: &Envelope) {
+        if likely(!envelope.is::<Monitor>()) {
+            return;
+        }
+
+        let watcher = NetworkAddr::from_remote(envelope.sender());
+
+        // `addr` is restored by the watcher from the sender.
+        let message = ActorDown::new(Addr::NULL, None, ActorDownReason::NotFound);
+        let kind = MessageKind::Regular { sender: recipient };
+        let envelope = Envelope::new(message.upcast(), kind);
+
+        if likely(self.tx_flows.do_acquire(watcher)) {
+            let _ = self.tx.try_send(KanalItem::simple(watcher, envelope));
+        }
+    }
 }
 
 fn make_system_envelope(message: impl Message) -> Envelope {
@@ -825,36 +875,53 @@ impl KanalItem {
 struct RemoteHandle {
     tx: kanal::AsyncSender<KanalItem>,
     tx_flows: Arc<TxFlows>,
+    monitors: Arc<Mutex<RemoteMonitors>>,
+}
+
+impl RemoteHandle {
+    fn on_enqueued(&self, change: Option<MonitorsChange>) {
+        if let Some(change) = change {
+            self.monitors.lock().apply(change);
+        }
+    }
 }
 
 impl remote::RemoteHandle for RemoteHandle {
     fn send(&self, recipient: Addr, envelope: Envelope) -> remote::SendResult {
+        let change = MonitorsChange::outgoing(recipient, &envelope);
         let recipient = NetworkAddr::from_remote(recipient);
 
         match self.tx_flows.acquire(recipient) {
             Acquire::Done => {
                 let mut item = Some(KanalItem::simple(recipient, envelope));
                 match self.tx.try_send_option(&mut item) {
-                    Ok(true) => remote::SendResult::Ok,
-                    Ok(false) => unreachable!(),
-                    Err(_) => {
-                        remote::SendResult::Err(SendError(item.take().unwrap().envelope.unwrap()))
+                    Ok(true) => {
+                        self.on_enqueued(change);
+                        remote::SendResult::Ok
                     }
+                    Ok(false) => unreachable!(),
+                    Err(_) => remote::SendResult::Err(SendError::Closed(
+                        item.take().unwrap().envelope.unwrap(),
+                    )),
                 }
             }
             Acquire::Full(notified) => remote::SendResult::Wait(notified, envelope),
-            Acquire::Closed => remote::SendResult::Err(SendError(envelope)),
+            Acquire::Closed => remote::SendResult::Err(SendError::Closed(envelope)),
         }
     }
 
     fn try_send(&self, recipient: Addr, envelope: Envelope) -> Result<(), TrySendError<Envelope>> {
+        let change = MonitorsChange::outgoing(recipient, &envelope);
         let recipient = NetworkAddr::from_remote(recipient);
 
         match self.tx_flows.try_acquire(recipient) {
             TryAcquire::Done => {
                 let mut item = Some(KanalItem::simple(recipient, envelope));
                 match self.tx.try_send_option(&mut item) {
-                    Ok(true) => Ok(()),
+                    Ok(true) => {
+                        self.on_enqueued(change);
+                        Ok(())
+                    }
                     Ok(false) => unreachable!(),
                     Err(_) => Err(TrySendError::Closed(item.take().unwrap().envelope.unwrap())),
                 }
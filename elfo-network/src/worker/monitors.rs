@@ -0,0 +1,51 @@
+use fxhash::FxHashSet;
+
+use elfo_core::{
+    messages::{Demonitor, Monitor},
+    Addr, Envelope,
+};
+
+/// Tracks local actors monitoring remote ones through this connection
+/// in order to notify them if the connection is lost.
+#[derive(Default)]
+pub(super) struct RemoteMonitors {
+    /// Pairs of `(watcher, monitored)`.
+    set: FxHashSet<(Addr, Addr)>,
+}
+
+pub(super) enum Change {
+    Add(Addr, Addr),
+    Remove(Addr, Addr),
+}
+
+impl Change {
+    /// Detects `Monitor` and `Demonitor` sent by local actors.
+    pub(super) fn outgoing(recipient: Addr, envelope: &Envelope) -> Option<Self> {
+        if envelope.is::<Monitor>() {
+            Some(Self::Add(envelope.sender(), recipient))
+        } else if envelope.is::<Demonitor>() {
+            Some(Self::Remove(envelope.sender(), recipient))
+        } else {
+            None
+        }
+    }
+}
+
+impl RemoteMonitors {
+    pub(super) fn apply(&mut self, change: Change) {
+        match change {
+            Change::Add(watcher, monitored) => {
+                debug_assert!(watcher.is_local());
+                debug_assert!(monitored.is_remote());
+                self.set.insert((watcher, monitored));
+            }
+            Change::Remove(watcher, monitored) => {
+                self.set.remove(&(watcher, monitored));
+            }
+        }
+    }
+
+    pub(super) fn drain(&mut self) -> impl Iterator<Item = (Addr, Addr)> + '_ {
+        self.set.drain()
+    }
+}
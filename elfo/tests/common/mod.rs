@@ -0,0 +1,31 @@
+//! Fixtures shared by integration tests running several groups.
+
+#![allow(dead_code)] // Every test uses only a part of fixtures.
+
+use std::sync::Arc;
+
+use futures_intrusive::channel::shared;
+
+use elfo::{config::AnyConfig, Topology, _priv::do_start};
+
+/// Mounts configurers with the default config.
+pub fn mount_configurers(topology: &Topology) {
+    let configurers = topology.local("system.configurers").entrypoint();
+    configurers.mount(elfo_configurer::fixture(topology, AnyConfig::default()));
+}
+
+/// Mounts configurers with the default config and starts the topology.
+pub async fn start(topology: Topology) {
+    mount_configurers(&topology);
+
+    do_start(topology, false, |_, _| futures::future::ready(()))
+        .await
+        .expect("cannot start");
+}
+
+/// Creates a channel to pass observations from actors to the test.
+/// The sender is shared to be cloned into `exec` closures.
+pub fn channel<T: Send>() -> (Arc<shared::Sender<T>>, shared::Receiver<T>) {
+    let (tx, rx) = shared::unbuffered_channel();
+    (Arc::new(tx), rx)
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "test-util")]
+
+use std::fs;
+
+use serde::Deserialize;
+
+use elfo::{prelude::*, Topology};
+use elfo_configurer::ConfigSources;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[allow(dead_code)]
+    count: u32,
+}
+
+async fn check(sources: ConfigSources) -> Vec<(String, String)> {
+    let topology = Topology::empty();
+    let configurers = topology.local("system.configurers").entrypoint();
+    let workers = topology.local("workers");
+
+    configurers.mount(elfo_configurer::from_sources(&topology, sources));
+    workers.mount(ActorGroup::new().config::<Config>().exec(|_| async {}));
+
+    match elfo::init::check_only(topology).await {
+        Ok(()) => Vec::new(),
+        Err(err) => err
+            .errors
+            .into_iter()
+            .map(|err| (err.group, err.reason))
+            .collect(),
+    }
+}
+
+#[tokio::test]
+async fn source_of_invalid_value() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.toml");
+    let prod = dir.path().join("prod.toml");
+    fs::write(&base, "workers.count = 1").unwrap();
+    fs::write(&prod, "workers.extra = 2").unwrap();
+
+    let sources = ConfigSources::new().file(&base);
+    assert!(check(sources.clone()).await.is_empty());
+
+    let errors = check(sources.optional_file(&prod)).await;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, "workers");
+    assert!(errors[0].1.contains("unknown field `extra`"));
+    assert!(errors[0]
+        .1
+        .contains(&format!("`workers.extra` is set by {}", prod.display())));
+}
+
+#[tokio::test]
+async fn source_of_invalid_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.toml");
+    fs::write(&base, "workers.count = ").unwrap();
+
+    let errors = check(ConfigSources::new().file(&base)).await;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, "system.configurers");
+    assert!(errors[0].1.starts_with(&base.display().to_string()));
+}
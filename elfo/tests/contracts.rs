@@ -0,0 +1,98 @@
+#![cfg(feature = "test-util")]
+
+use std::{io, sync::Arc};
+
+use parking_lot::Mutex;
+
+use elfo::{config::AnyConfig, prelude::*, Topology};
+
+#[message]
+struct Tick;
+
+#[message]
+struct Tock;
+
+fn topology(emitted: Blueprint) -> Topology {
+    let topology = Topology::empty();
+    let configurers = topology.local("system.configurers").entrypoint();
+    let producers = topology.local("producers");
+    let consumers = topology.local("consumers");
+
+    producers.route_to(&consumers, |envelope| envelope.is::<Tick>());
+
+    configurers.mount(elfo_configurer::fixture(&topology, AnyConfig::default()));
+    producers.mount(emitted);
+    consumers.mount(ActorGroup::new().accepts::<Tick>().exec(|_| async {}));
+    topology
+}
+
+#[tokio::test]
+async fn routed() {
+    let producers = ActorGroup::new().emits::<Tick>().exec(|_| async {});
+    elfo::init::check_only(topology(producers)).await.unwrap();
+}
+
+#[tokio::test]
+async fn unrouted() {
+    let producers = ActorGroup::new()
+        .emits::<Tick>()
+        .emits::<Tock>()
+        .exec(|_| async {});
+
+    let err = elfo::init::check_only(topology(producers))
+        .await
+        .unwrap_err();
+    assert_eq!(err.errors.len(), 1);
+    assert_eq!(err.errors[0].group, "producers");
+    assert!(err.errors[0].reason.contains("Tock"));
+}
+
+#[derive(Clone, Default)]
+struct Logs(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Logs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn dead_route() {
+    let logs = Logs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let topology = Topology::empty();
+    let configurers = topology.local("system.configurers").entrypoint();
+    let producers = topology.local("producers");
+    let consumers = topology.local("consumers");
+    let others = topology.local("others");
+
+    // `consumers` don't accept `Tock`, so the route to them is dead.
+    producers.route_to(&consumers, |_| true);
+    producers.route_to(&others, |_| true);
+
+    configurers.mount(elfo_configurer::fixture(&topology, AnyConfig::default()));
+    producers.mount(ActorGroup::new().emits::<Tock>().exec(|_| async {}));
+    consumers.mount(ActorGroup::new().accepts::<Tick>().exec(|_| async {}));
+    others.mount(ActorGroup::new().accepts::<Tock>().exec(|_| async {}));
+
+    elfo::init::check_only(topology).await.unwrap();
+
+    let logs = String::from_utf8(logs.0.lock().clone()).unwrap();
+    let dead_routes = logs
+        .lines()
+        .filter(|line| line.contains("dead route"))
+        .collect::<Vec<_>>();
+    assert_eq!(dead_routes.len(), 1);
+    assert!(dead_routes[0].contains("to=consumers"));
+}
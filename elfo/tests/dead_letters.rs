@@ -0,0 +1,80 @@
+#![cfg(feature = "test-util")]
+
+use elfo::{
+    messages::{DeadLetter, DeadLetterReason},
+    prelude::*,
+    routers::{MapRouter, Outcome},
+    Topology,
+};
+
+mod common;
+
+#[message]
+struct Lost(u32);
+
+#[message]
+struct Orphan;
+
+#[message]
+struct Unroutable;
+
+#[tokio::test]
+async fn dead_letters() {
+    let (tx, rx) = common::channel();
+
+    let topology = Topology::empty();
+    let senders = topology.local("senders");
+    let discarders = topology.local("discarders");
+    let dead_letters = topology.local("dead_letters").dead_letters();
+
+    senders.route_to(&discarders, |envelope| {
+        envelope.is::<Lost>() || envelope.is::<Orphan>()
+    });
+
+    senders.mount(ActorGroup::new().exec(|ctx| async move {
+        assert!(ctx.send(Lost(42)).await.is_err());
+        assert!(ctx.send(Orphan).await.is_err());
+        assert!(ctx.try_send(Unroutable).is_err());
+    }));
+    discarders.mount(
+        ActorGroup::new()
+            .router(MapRouter::new(|envelope| {
+                msg!(match envelope {
+                    Lost => Outcome::Discard,
+                    Orphan => Outcome::HealthyUnicast(vec![1]),
+                    _ => Outcome::Default,
+                })
+            }))
+            .exec(|_ctx: Context<(), u32>| async move {
+                panic!("discarders should not be started");
+            }),
+    );
+    dead_letters.mount(ActorGroup::new().exec(move |mut ctx| {
+        let tx = tx.clone();
+
+        async move {
+            while let Some(envelope) = ctx.recv().await {
+                msg!(match envelope {
+                    letter @ DeadLetter => tx.send(letter).await.unwrap(),
+                });
+            }
+        }
+    }));
+
+    common::start(topology).await;
+
+    let letter = rx.receive().await.unwrap();
+    assert_eq!(letter.reason, DeadLetterReason::Discarded);
+    assert!(!letter.sender.is_null());
+    assert!(letter.recipient.is_null());
+    let Lost(num) = letter.message.downcast::<Lost>().unwrap();
+    assert_eq!(num, 42);
+
+    let letter = rx.receive().await.unwrap();
+    assert_eq!(letter.reason, DeadLetterReason::Unhealthy);
+    assert!(letter.message.is::<Orphan>());
+
+    let letter = rx.receive().await.unwrap();
+    assert_eq!(letter.reason, DeadLetterReason::Unroutable);
+    assert!(letter.message.is::<Unroutable>());
+}
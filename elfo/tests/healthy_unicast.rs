@@ -0,0 +1,72 @@
+#![cfg(feature = "test-util")]
+
+use std::time::Duration;
+
+use elfo::{
+    config::AnyConfig,
+    prelude::*,
+    routers::{MapRouter, Outcome},
+    RestartParams, RestartPolicy,
+};
+
+#[message]
+struct Start(u32);
+
+#[message]
+struct Crash(u32);
+
+#[message]
+struct Job(Vec<u32>);
+
+#[message]
+#[derive(PartialEq)]
+struct Done(u32);
+
+fn workers() -> Blueprint {
+    ActorGroup::new()
+        .router(MapRouter::new(|envelope| {
+            msg!(match envelope {
+                Start(key) | Crash(key) => Outcome::Unicast(*key),
+                Job(keys) => Outcome::HealthyUnicast(keys.clone()),
+                _ => Outcome::Default,
+            })
+        }))
+        .restart_policy(RestartPolicy::on_failure(RestartParams::new(
+            Duration::from_secs(60),
+            Duration::from_secs(60),
+        )))
+        .exec(|mut ctx: Context<(), u32>| async move {
+            while let Some(envelope) = ctx.recv().await {
+                msg!(match envelope {
+                    Start => {}
+                    Crash => panic!("boom!"),
+                    Job => ctx.send(Done(*ctx.key())).await.unwrap(),
+                });
+            }
+        })
+}
+
+#[tokio::test(start_paused = true)]
+async fn healthy_unicast() {
+    let mut proxy = elfo::test::proxy(workers(), AnyConfig::default()).await;
+
+    // No actors are started.
+    assert!(proxy.try_send(Job(vec![1, 2])).unwrap_err().is_unhealthy());
+
+    proxy.send(Start(1)).await;
+    proxy.send(Start(2)).await;
+    proxy.sync().await;
+
+    proxy.send(Job(vec![1, 2])).await;
+    assert_msg_eq!(proxy.recv().await, Done(1));
+
+    // The failed actor waits for restart, so the fallback key is used.
+    proxy.send(Crash(1)).await;
+    proxy.sync().await;
+    proxy.send(Job(vec![1, 2])).await;
+    assert_msg_eq!(proxy.recv().await, Done(2));
+
+    // No healthy actors.
+    assert!(proxy.try_send(Job(vec![1])).unwrap_err().is_unhealthy());
+    assert!(proxy.try_send(Job(vec![3])).unwrap_err().is_unhealthy());
+}
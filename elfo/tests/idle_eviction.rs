@@ -0,0 +1,122 @@
+#![cfg(feature = "test-util")]
+
+use std::time::Duration;
+
+use elfo::{
+    config::AnyConfig,
+    prelude::*,
+    routers::{MapRouter, Outcome},
+};
+
+#[message]
+struct Touch(u32);
+
+#[message]
+#[derive(PartialEq)]
+struct Started {
+    key: u32,
+    evicted: bool,
+}
+
+fn keyed() -> Blueprint {
+    keyed_with_cleanup(Duration::ZERO)
+}
+
+// The actor is busy for `cleanup` after its mailbox is closed.
+fn keyed_with_cleanup(cleanup: Duration) -> Blueprint {
+    ActorGroup::new()
+        .router(MapRouter::new(|envelope| {
+            msg!(match envelope {
+                Touch(key) => Outcome::Unicast(*key),
+                _ => Outcome::Default,
+            })
+        }))
+        .idle_timeout(Duration::from_secs(10))
+        .exec(move |mut ctx: Context<(), u32>| async move {
+            let evicted = ctx.start_info().cause.is_evicted();
+            ctx.send(Started {
+                key: *ctx.key(),
+                evicted,
+            })
+            .await
+            .unwrap();
+
+            while ctx.recv().await.is_some() {}
+            tokio::time::sleep(cleanup).await;
+        })
+}
+
+#[tokio::test(start_paused = true)]
+async fn idle_actors_are_evicted() {
+    let mut proxy = elfo::test::proxy(keyed(), AnyConfig::default()).await;
+
+    proxy.send(Touch(1)).await;
+    assert_msg_eq!(
+        proxy.recv().await,
+        Started {
+            key: 1,
+            evicted: false
+        }
+    );
+
+    // Every received message resets the idle timer.
+    for _ in 0..3 {
+        tokio::time::sleep(Duration::from_secs(9)).await;
+        proxy.send(Touch(1)).await;
+        proxy.sync().await;
+        assert!(proxy.try_recv().await.is_none());
+    }
+
+    tokio::time::sleep(Duration::from_secs(11)).await;
+    proxy.sync().await;
+
+    proxy.send(Touch(1)).await;
+    assert_msg_eq!(
+        proxy.recv().await,
+        Started {
+            key: 1,
+            evicted: true
+        }
+    );
+
+    // Other keys aren't affected.
+    proxy.send(Touch(2)).await;
+    assert_msg_eq!(
+        proxy.recv().await,
+        Started {
+            key: 2,
+            evicted: false
+        }
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn messages_during_eviction_are_kept() {
+    let cleanup = Duration::from_secs(5);
+    let mut proxy = elfo::test::proxy(keyed_with_cleanup(cleanup), AnyConfig::default()).await;
+
+    proxy.send(Touch(1)).await;
+    assert_msg_eq!(
+        proxy.recv().await,
+        Started {
+            key: 1,
+            evicted: false
+        }
+    );
+
+    // The actor is evicted, but hasn't finished yet.
+    tokio::time::sleep(Duration::from_secs(11)).await;
+    proxy.send(Touch(1)).await;
+    proxy.sync().await;
+    assert!(proxy.try_recv().await.is_none());
+
+    // Started again right after finishing.
+    tokio::time::sleep(cleanup).await;
+    assert_msg_eq!(
+        proxy.recv().await,
+        Started {
+            key: 1,
+            evicted: true
+        }
+    );
+}
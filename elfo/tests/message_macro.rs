@@ -30,6 +30,9 @@ struct SimpleMessageWithOverridedProtocol {}
 #[message(protocol = "override", ret = ())]
 struct SimpleRequestWithOverridedProtocol {}
 
+#[message(ret = (), priority = "high")]
+struct HighPriorityRequest {}
+
 mod one {
     use super::*;
 
@@ -109,6 +112,15 @@ fn protocol() {
     assert_eq!(elfo::messages::Ping::default().protocol(), "elfo-core");
 }
 
+#[test]
+fn priority() {
+    assert!(!SimpleMessage {}.high_priority());
+    assert!(!SimpleRequest {}.high_priority());
+    assert!(HighPriorityRequest {}.high_priority());
+    assert!(elfo::messages::Ping::default().high_priority());
+    assert!(elfo::messages::Terminate::default().high_priority());
+}
+
 #[test]
 fn uniqueness() {
     // Duplicate message definition.
@@ -0,0 +1,75 @@
+#![cfg(feature = "test-util")]
+
+use elfo::{
+    messages::{ActorDown, ActorDownReason},
+    prelude::*,
+    ActorStatusKind, Topology,
+};
+
+mod common;
+
+#[message]
+struct Hello;
+
+#[message]
+struct Stop;
+
+#[tokio::test]
+async fn monitor() {
+    let (tx, rx) = common::channel();
+
+    let topology = Topology::empty();
+    let watchers = topology.local("watchers");
+    let workers = topology.local("workers");
+
+    workers.route_to(&watchers, |envelope| envelope.is::<Hello>());
+    watchers.route_to(&workers, |envelope| envelope.is::<Stop>());
+
+    workers.mount(ActorGroup::new().exec(|mut ctx| async move {
+        ctx.send(Hello).await.unwrap();
+
+        while let Some(envelope) = ctx.recv().await {
+            msg!(match envelope {
+                Stop => break,
+            });
+        }
+    }));
+    watchers.mount(ActorGroup::new().exec(move |mut ctx| {
+        let tx = tx.clone();
+
+        async move {
+            while let Some(envelope) = ctx.recv().await {
+                let worker = envelope.sender();
+
+                msg!(match envelope {
+                    Hello => {
+                        // Repeated monitoring produces only one notification.
+                        ctx.monitor(worker).await;
+                        ctx.monitor(worker).await;
+                        ctx.send(Stop).await.unwrap();
+                    }
+                    down @ ActorDown => {
+                        assert_eq!(down.addr, worker);
+
+                        if down.reason == ActorDownReason::Finished {
+                            // The worker is gone, so it cannot be monitored anymore.
+                            ctx.monitor(worker).await;
+                        }
+
+                        tx.send(down).await.unwrap();
+                    }
+                });
+            }
+        }
+    }));
+
+    common::start(topology).await;
+
+    let down = rx.receive().await.unwrap();
+    assert_eq!(down.reason, ActorDownReason::Finished);
+    assert_eq!(down.status.unwrap().kind(), ActorStatusKind::Terminated);
+
+    let down = rx.receive().await.unwrap();
+    assert_eq!(down.reason, ActorDownReason::NotFound);
+    assert!(down.status.is_none());
+}
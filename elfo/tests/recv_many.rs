@@ -0,0 +1,67 @@
+#![cfg(feature = "test-util")]
+
+use elfo::{config::AnyConfig, prelude::*};
+
+#[message]
+struct Item;
+
+#[message]
+struct Close;
+
+#[message]
+#[derive(PartialEq)]
+struct Batch(usize);
+
+#[message]
+struct Finished;
+
+fn batcher() -> Blueprint {
+    ActorGroup::new().exec(|mut ctx| async move {
+        let mut batch = Vec::new();
+
+        loop {
+            let count = ctx.recv_many(&mut batch, 3).await;
+            if count == 0 {
+                break;
+            }
+
+            assert_eq!(batch.len(), count);
+            for envelope in batch.drain(..) {
+                msg!(match envelope {
+                    Close => assert!(ctx.close()),
+                });
+            }
+
+            ctx.send(Batch(count)).await.unwrap();
+        }
+
+        ctx.send(Finished).await.unwrap();
+    })
+}
+
+#[tokio::test]
+async fn batches() {
+    let mut proxy = elfo::test::proxy(batcher(), AnyConfig::default()).await;
+
+    for _ in 0..5 {
+        proxy.send(Item).await;
+    }
+
+    assert_msg_eq!(proxy.recv().await, Batch(3));
+    assert_msg_eq!(proxy.recv().await, Batch(2));
+}
+
+#[tokio::test]
+async fn closed() {
+    let mut proxy = elfo::test::proxy(batcher(), AnyConfig::default()).await;
+
+    proxy.send(Item).await;
+    proxy.send(Close).await;
+    proxy.send(Item).await;
+    proxy.send(Item).await;
+
+    // The last item is received before the closed mailbox is reported.
+    assert_msg_eq!(proxy.recv().await, Batch(3));
+    assert_msg_eq!(proxy.recv().await, Batch(1));
+    assert_msg!(proxy.recv().await, Finished);
+}
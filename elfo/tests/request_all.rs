@@ -0,0 +1,140 @@
+#![cfg(feature = "test-util")]
+
+use std::time::Duration;
+
+use futures::{pin_mut, StreamExt};
+
+use elfo::{
+    errors::RequestError,
+    prelude::*,
+    routers::{MapRouter, Outcome},
+    Topology,
+};
+
+mod common;
+
+#[message(ret = u32)]
+struct Query;
+
+// Actors with keys `0` and `1` respond immediately, `2` never responds.
+fn responders() -> Blueprint {
+    ActorGroup::new()
+        .router(MapRouter::new(|envelope| {
+            msg!(match envelope {
+                Query => Outcome::Multicast(vec![0, 1, 2]),
+                _ => Outcome::Default,
+            })
+        }))
+        .exec(|mut ctx: Context<(), u32>| async move {
+            let mut delayed = Vec::new();
+
+            while let Some(envelope) = ctx.recv().await {
+                msg!(match envelope {
+                    (Query, token) => {
+                        if *ctx.key() == 2 {
+                            delayed.push(token);
+                        } else {
+                            ctx.respond(token, *ctx.key());
+                        }
+                    }
+                });
+            }
+        })
+}
+
+async fn start(requester: Blueprint) {
+    let topology = Topology::empty();
+    let requesters = topology.local("requesters");
+    let responders_ = topology.local("responders");
+
+    requesters.route_all_to(&responders_);
+
+    requesters.mount(requester);
+    responders_.mount(responders());
+
+    common::start(topology).await;
+}
+
+#[tokio::test]
+async fn resolve_stream() {
+    let (tx, rx) = common::channel();
+
+    start(ActorGroup::new().exec(move |ctx| {
+        let tx = tx.clone();
+
+        async move {
+            let stream = ctx.request(Query).all().resolve_stream();
+            pin_mut!(stream);
+
+            let mut received = Vec::new();
+            for _ in 0..2 {
+                let (addr, response) = stream.next().await.unwrap();
+                assert!(!addr.is_null());
+                received.push(response.unwrap());
+            }
+
+            received.sort_unstable();
+            tx.send(received).await.unwrap();
+        }
+    }))
+    .await;
+
+    assert_eq!(rx.receive().await.unwrap(), vec![0, 1]);
+}
+
+#[tokio::test]
+async fn resolve_quorum() {
+    let (tx, rx) = common::channel();
+
+    start(ActorGroup::new().exec(move |ctx| {
+        let tx = tx.clone();
+
+        async move {
+            let responses = ctx.request(Query).all().resolve_quorum(2).await;
+            let mut received = responses
+                .into_iter()
+                .map(|(addr, response)| {
+                    assert!(!addr.is_null());
+                    response
+                })
+                .collect::<Vec<_>>();
+
+            received.sort_unstable();
+            tx.send(received).await.unwrap();
+        }
+    }))
+    .await;
+
+    assert_eq!(rx.receive().await.unwrap(), vec![0, 1]);
+}
+
+#[tokio::test]
+async fn timeouts_are_attributed_to_recipients() {
+    let (tx, rx) = common::channel();
+
+    start(ActorGroup::new().exec(move |ctx| {
+        let tx = tx.clone();
+
+        async move {
+            let stream = (ctx.request(Query).all())
+                .timeout(Duration::from_millis(100))
+                .resolve_stream();
+
+            tx.send(stream.collect::<Vec<_>>().await).await.unwrap();
+        }
+    }))
+    .await;
+
+    let responses = rx.receive().await.unwrap();
+    assert_eq!(responses.len(), 3);
+
+    let (timed_out, responded): (Vec<_>, Vec<_>) = responses
+        .into_iter()
+        .partition(|(_, response)| response.is_err());
+
+    assert_eq!(timed_out.len(), 1);
+    let (addr, response) = &timed_out[0];
+    assert!(matches!(response, Err(RequestError::Timeout)));
+    assert!(!addr.is_null());
+    assert!(responded.iter().all(|(responder, _)| responder != addr));
+}
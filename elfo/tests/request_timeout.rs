@@ -0,0 +1,83 @@
+#![cfg(feature = "test-util")]
+
+use std::time::Duration;
+
+use elfo::{config::AnyConfig, errors::RequestError, prelude::*};
+
+#[message(ret = u32)]
+struct Question;
+
+#[message]
+struct Ask;
+
+#[message]
+#[derive(PartialEq)]
+enum Answer {
+    Ok(u32),
+    Failed,
+    Ignored,
+    Timeout,
+}
+
+fn requester() -> Blueprint {
+    ActorGroup::new().exec(|mut ctx| async move {
+        while let Some(envelope) = ctx.recv().await {
+            msg!(match envelope {
+                Ask => {
+                    let answer = match ctx
+                        .request(Question)
+                        .timeout(Duration::from_secs(5))
+                        .resolve()
+                        .await
+                    {
+                        Ok(num) => Answer::Ok(num),
+                        Err(RequestError::Failed) => Answer::Failed,
+                        Err(RequestError::Ignored) => Answer::Ignored,
+                        Err(RequestError::Timeout) => Answer::Timeout,
+                    };
+                    ctx.send(answer).await.unwrap();
+                }
+            });
+        }
+    })
+}
+
+#[tokio::test(start_paused = true)]
+async fn in_time() {
+    let mut proxy = elfo::test::proxy(requester(), AnyConfig::default()).await;
+
+    proxy.send(Ask).await;
+    msg!(match proxy.recv().await {
+        (Question, token) => {
+            tokio::time::sleep(Duration::from_secs(4)).await;
+            proxy.respond(token, 42);
+        }
+    });
+
+    assert_msg_eq!(proxy.recv().await, Answer::Ok(42));
+}
+
+#[tokio::test(start_paused = true)]
+async fn timed_out() {
+    let mut proxy = elfo::test::proxy(requester(), AnyConfig::default()).await;
+
+    proxy.send(Ask).await;
+    let token = msg!(match proxy.recv().await {
+        (Question, token) => token,
+        _ => unreachable!(),
+    });
+
+    tokio::time::sleep(Duration::from_secs(6)).await;
+    assert_msg_eq!(proxy.recv().await, Answer::Timeout);
+
+    // A late response must be discarded without affecting next requests.
+    proxy.respond(token, 42);
+    proxy.sync().await;
+    assert!(proxy.try_recv().await.is_none());
+
+    proxy.send(Ask).await;
+    msg!(match proxy.recv().await {
+        (Question, token) => proxy.respond(token, 43),
+    });
+    assert_msg_eq!(proxy.recv().await, Answer::Ok(43));
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "test-util")]
+
+use std::time::Duration;
+
+use elfo::{
+    config::AnyConfig,
+    messages::{ActorStatusReport, SubscribeToActorStatuses},
+    prelude::*,
+    ActorStatusKind, RestartIntensity, RestartParams, RestartPolicy,
+};
+
+#[message]
+struct Crash;
+
+#[message]
+struct Started;
+
+#[tokio::test]
+async fn group_fails_on_exceeded_intensity() {
+    let blueprint = ActorGroup::new()
+        .restart_policy(RestartPolicy::on_failure(RestartParams::new(
+            Duration::ZERO,
+            Duration::ZERO,
+        )))
+        .restart_intensity(RestartIntensity::new(3, Duration::from_secs(60)))
+        .exec(|mut ctx| async move {
+            ctx.send(Started).await.unwrap();
+
+            // Crash loop after the first failure.
+            if ctx.start_info().cause.is_restarted() {
+                panic!("boom!");
+            }
+
+            while let Some(envelope) = ctx.recv().await {
+                msg!(match envelope {
+                    Crash => panic!("boom!"),
+                });
+            }
+        });
+
+    let mut proxy = elfo::test::proxy(blueprint, AnyConfig::default()).await;
+    proxy.send(SubscribeToActorStatuses::default()).await;
+    assert_msg!(proxy.recv().await, Started);
+    proxy.sync().await;
+    while proxy.try_recv().await.is_some() {}
+
+    proxy.send(Crash).await;
+
+    // The initial start and three restarts.
+    let mut started = 1;
+    loop {
+        msg!(match proxy.recv().await {
+            Started => started += 1,
+            report @ ActorStatusReport => {
+                // Skip reports of actors.
+                if report.meta.key.is_empty() {
+                    assert_eq!(report.status.kind(), ActorStatusKind::Failed);
+                    break;
+                }
+            }
+        });
+    }
+    assert_eq!(started, 4);
+
+    // The group doesn't start actors anymore.
+    assert!(proxy.try_send(Crash).is_err());
+}
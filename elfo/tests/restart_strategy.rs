@@ -0,0 +1,115 @@
+#![cfg(feature = "test-util")]
+
+use std::time::Duration;
+
+use elfo::{
+    config::AnyConfig,
+    prelude::*,
+    routers::{MapRouter, Outcome},
+    RestartParams, RestartPolicy, RestartStrategy,
+};
+
+#[message]
+struct Start(u32);
+
+#[message]
+struct Fail(u32);
+
+#[message]
+#[derive(PartialEq)]
+struct Started(u32);
+
+fn group(strategy: RestartStrategy, idle_timeout: Option<Duration>) -> Blueprint {
+    let mut group = ActorGroup::new()
+        .router(MapRouter::new(|envelope| {
+            msg!(match envelope {
+                Start(key) | Fail(key) => Outcome::Unicast(*key),
+                _ => Outcome::Default,
+            })
+        }))
+        .restart_policy(RestartPolicy::on_failure(RestartParams::new(
+            Duration::ZERO,
+            Duration::ZERO,
+        )))
+        .restart_strategy(strategy);
+
+    if let Some(timeout) = idle_timeout {
+        group = group.idle_timeout(timeout);
+    }
+
+    group.exec(|mut ctx: Context<(), u32>| async move {
+        ctx.send(Started(*ctx.key())).await.unwrap();
+
+        while let Some(envelope) = ctx.recv().await {
+            msg!(match envelope {
+                Start => {}
+                Fail => panic!("boom!"),
+            });
+        }
+    })
+}
+
+async fn check(strategy: RestartStrategy, expected: &[u32]) {
+    let mut proxy = elfo::test::proxy(group(strategy, None), AnyConfig::default()).await;
+
+    for key in 1..=3 {
+        proxy.send(Start(key)).await;
+        assert_msg!(proxy.recv().await, Started(_));
+    }
+
+    proxy.send(Fail(2)).await;
+
+    let mut keys = Vec::new();
+    for _ in expected {
+        msg!(match proxy.recv().await {
+            Started(key) => keys.push(key),
+        });
+    }
+    keys.sort_unstable();
+    assert_eq!(keys, expected);
+
+    proxy.sync().await;
+    assert!(proxy.try_recv().await.is_none());
+}
+
+#[tokio::test]
+async fn one_for_one() {
+    check(RestartStrategy::OneForOne, &[2]).await;
+}
+
+#[tokio::test]
+async fn one_for_all() {
+    check(RestartStrategy::OneForAll, &[1, 2, 3]).await;
+}
+
+#[tokio::test]
+async fn rest_for_one() {
+    check(RestartStrategy::RestForOne, &[2, 3]).await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn rest_for_one_after_eviction() {
+    let blueprint = group(RestartStrategy::RestForOne, Some(Duration::from_secs(10)));
+    let mut proxy = elfo::test::proxy(blueprint, AnyConfig::default()).await;
+
+    proxy.send(Start(1)).await;
+    assert_msg_eq!(proxy.recv().await, Started(1));
+
+    // The first actor is evicted, so it's started after the second one later.
+    tokio::time::sleep(Duration::from_secs(20)).await;
+    proxy.send(Start(2)).await;
+    assert_msg_eq!(proxy.recv().await, Started(2));
+    proxy.send(Start(1)).await;
+    assert_msg_eq!(proxy.recv().await, Started(1));
+
+    proxy.send(Fail(2)).await;
+
+    let mut keys = Vec::new();
+    for _ in 0..2 {
+        msg!(match proxy.recv().await {
+            Started(key) => keys.push(key),
+        });
+    }
+    keys.sort_unstable();
+    assert_eq!(keys, [1, 2]);
+}
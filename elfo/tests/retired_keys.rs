@@ -0,0 +1,115 @@
+#![cfg(feature = "test-util")]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use serde::Deserialize;
+use toml::toml;
+
+use elfo::{
+    config::AnyConfig, messages::UpdateConfig, prelude::*, routers::PoolRouter, RestartParams,
+    RestartPolicy, TerminationPolicy,
+};
+
+#[message]
+#[derive(PartialEq)]
+struct Started(usize);
+
+#[message]
+#[derive(PartialEq)]
+struct Stopped(usize);
+
+#[derive(Debug, Clone, Deserialize)]
+struct Config {
+    pool_size: usize,
+}
+
+#[tokio::test(start_paused = true)]
+async fn retired_actors_are_terminated() {
+    let blueprint = ActorGroup::new()
+        .config::<Config>()
+        .router(PoolRouter::round_robin(|config: &Config| config.pool_size))
+        .restart_policy(RestartPolicy::always(RestartParams::new(
+            Duration::from_secs(1),
+            Duration::from_secs(1),
+        )))
+        .exec(|mut ctx: Context<Config, usize>| async move {
+            let key = *ctx.key();
+            ctx.send(Started(key)).await.unwrap();
+            while ctx.recv().await.is_some() {}
+            ctx.send(Stopped(key)).await.unwrap();
+        });
+
+    let mut proxy = elfo::test::proxy(blueprint, toml! { pool_size = 3 }).await;
+
+    let mut started = Vec::new();
+    for _ in 0..3 {
+        msg!(match proxy.recv().await {
+            Started(key) => started.push(key),
+        });
+    }
+    started.sort_unstable();
+    assert_eq!(started, [0, 1, 2]);
+
+    let config = AnyConfig::deserialize(toml! { pool_size = 1 }).unwrap();
+    proxy.send(UpdateConfig::new(config)).await;
+
+    let mut stopped = Vec::new();
+    for _ in 0..2 {
+        msg!(match proxy.recv().await {
+            Stopped(key) => stopped.push(key),
+        });
+    }
+    stopped.sort_unstable();
+    assert_eq!(stopped, [1, 2]);
+
+    // Retired actors aren't restarted.
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    proxy.sync().await;
+    assert!(proxy.try_recv().await.is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn retired_actors_are_aborted_after_deadline() {
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let blueprint = ActorGroup::new()
+        .config::<Config>()
+        .router(PoolRouter::round_robin(|config: &Config| config.pool_size))
+        .termination_policy(TerminationPolicy::manually().deadline(Duration::from_secs(10)))
+        .exec(|mut ctx: Context<Config, usize>| async move {
+            let _guard = Guard;
+            ctx.send(Started(*ctx.key())).await.unwrap();
+
+            // `Terminate` is ignored.
+            while ctx.recv().await.is_some() {}
+        });
+
+    let mut proxy = elfo::test::proxy(blueprint, toml! { pool_size = 2 }).await;
+    for _ in 0..2 {
+        assert!(proxy.recv().await.is::<Started>());
+    }
+
+    let config = AnyConfig::deserialize(toml! { pool_size = 1 }).unwrap();
+    proxy.send(UpdateConfig::new(config)).await;
+
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    proxy.sync().await;
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 0);
+
+    // Only the retired actor is aborted.
+    tokio::time::sleep(Duration::from_secs(6)).await;
+    proxy.sync().await;
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+    assert!(proxy.try_recv().await.is_none());
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "test-util")]
+
+use std::{sync::Arc, time::Duration};
+
+use elfo::{config::AnyConfig, prelude::*, Topology, _priv::do_start};
+
+#[message]
+struct Tick;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Started,
+    Ticked,
+    Stopped,
+}
+
+#[tokio::test]
+async fn mount_and_unmount_at_runtime() {
+    let (tx, rx) = futures_intrusive::channel::shared::channel(100);
+    let tx = Arc::new(tx);
+
+    let topology = Topology::empty();
+    let configurers = topology.local("system.configurers").entrypoint();
+    let producers = topology.local("producers");
+
+    configurers.mount(elfo_configurer::fixture(&topology, AnyConfig::default()));
+    producers.mount(ActorGroup::new().exec(|ctx| async move {
+        loop {
+            // Fails until the plugin is mounted.
+            let _ = ctx.send(Tick).await;
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }));
+
+    do_start(topology.clone(), false, |_, _| futures::future::ready(()))
+        .await
+        .expect("cannot start");
+
+    let plugin = move || {
+        let tx = tx.clone();
+        ActorGroup::new().exec(move |mut ctx| {
+            let tx = tx.clone();
+
+            async move {
+                tx.send(Event::Started).await.unwrap();
+                while let Some(envelope) = ctx.recv().await {
+                    msg!(match envelope {
+                        Tick => {
+                            let _ = tx.try_send(Event::Ticked);
+                        }
+                    });
+                }
+                tx.send(Event::Stopped).await.unwrap();
+            }
+        })
+    };
+
+    for _ in 0..2 {
+        let producers = topology
+            .locals()
+            .find(|group| group.name == "producers")
+            .unwrap();
+
+        let local = topology.local("plugin");
+        local.route_from(&producers, |envelope| envelope.is::<Tick>());
+        local.mount(plugin());
+
+        assert!(topology.locals().any(|group| group.name == "plugin"));
+        assert_eq!(rx.receive().await, Some(Event::Started));
+        assert_eq!(rx.receive().await, Some(Event::Ticked));
+
+        assert!(topology.unmount("plugin").await);
+        assert!(!topology.locals().any(|group| group.name == "plugin"));
+        while let Some(event) = rx.receive().await {
+            if event == Event::Stopped {
+                break;
+            }
+        }
+    }
+
+    assert!(!topology.unmount("plugin").await);
+}
@@ -53,3 +53,54 @@ async fn it_works() {
     assert_msg!(proxy.recv().await, OnMessage);
     assert_msg!(proxy.recv().await, Restarted);
 }
+
+#[tokio::test]
+async fn previous_run() {
+    #[message]
+    struct Crash;
+
+    #[message]
+    #[derive(PartialEq)]
+    struct Started {
+        attempt: Option<u64>,
+        details: Option<String>,
+    }
+
+    let blueprint = ActorGroup::new()
+        .restart_policy(RestartPolicy::on_failure(RestartParams::new(
+            Duration::from_secs(0),
+            Duration::from_secs(0),
+        )))
+        .exec(move |mut ctx| async move {
+            let previous = ctx.start_info().previous.as_ref();
+            let attempt = previous.map(|p| p.attempt);
+            let details = previous.and_then(|p| p.status.details().map(String::from));
+            ctx.send(Started { attempt, details }).await.unwrap();
+
+            while let Some(envelope) = ctx.recv().await {
+                msg!(match envelope {
+                    Crash => panic!("boom #{}", attempt.unwrap_or(0)),
+                });
+            }
+        });
+
+    let mut proxy = elfo::test::proxy(blueprint, elfo::config::AnyConfig::default()).await;
+    assert_msg_eq!(
+        proxy.recv().await,
+        Started {
+            attempt: None,
+            details: None
+        }
+    );
+
+    for attempt in 1..=2 {
+        proxy.send(Crash).await;
+        assert_msg_eq!(
+            proxy.recv().await,
+            Started {
+                attempt: Some(attempt),
+                details: Some(format!("panic: boom #{}", attempt - 1)),
+            }
+        );
+    }
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "test-util")]
+
+use elfo::{errors::StashError, prelude::*};
+use toml::toml;
+
+#[message]
+struct Job(u32);
+
+#[message]
+struct Ready;
+
+#[message]
+#[derive(PartialEq)]
+struct Done(u32);
+
+#[message]
+#[derive(PartialEq)]
+struct Rejected(u32);
+
+#[tokio::test]
+async fn stash() {
+    let config = toml! {
+        [system.mailbox]
+        stash_capacity = 2
+    };
+
+    let blueprint = ActorGroup::new().exec(|mut ctx| async move {
+        let mut is_ready = false;
+
+        while let Some(envelope) = ctx.recv().await {
+            if !is_ready && envelope.is::<Job>() {
+                if let Err(StashError(envelope)) = ctx.stash(envelope) {
+                    msg!(match envelope {
+                        Job(num) => ctx.send(Rejected(num)).await.unwrap(),
+                    });
+                }
+                continue;
+            }
+
+            msg!(match envelope {
+                Ready => {
+                    is_ready = true;
+                    ctx.unstash_all();
+                }
+                Job(num) => ctx.send(Done(num)).await.unwrap(),
+            });
+        }
+    });
+
+    let mut proxy = elfo::test::proxy(blueprint, config).await;
+
+    for num in 1..=3 {
+        proxy.send(Job(num)).await;
+    }
+    assert_msg_eq!(proxy.recv().await, Rejected(3));
+
+    proxy.send(Ready).await;
+    proxy.send(Job(4)).await;
+
+    // Stashed envelopes are received in the original order before new ones.
+    assert_msg_eq!(proxy.recv().await, Done(1));
+    assert_msg_eq!(proxy.recv().await, Done(2));
+    assert_msg_eq!(proxy.recv().await, Done(4));
+}
@@ -1,7 +1,13 @@
 #![cfg(feature = "test-util")]
 #![allow(clippy::never_loop)]
 
-use elfo::{messages::Terminate, prelude::*, TerminationPolicy};
+use std::time::Duration;
+
+use elfo::{
+    messages::{ActorStatusReport, SubscribeToActorStatuses, Terminate},
+    prelude::*,
+    ActorStatusKind, TerminationPolicy,
+};
 
 #[message]
 #[derive(PartialEq)]
@@ -79,3 +85,42 @@ async fn it_terminates_manually_policy_via_closing_terminate() {
     proxy.finished().await;
     proxy.sync().await;
 }
+
+#[tokio::test(start_paused = true)]
+async fn it_aborts_actors_after_deadline() {
+    let blueprint = ActorGroup::new()
+        .termination_policy(TerminationPolicy::manually().deadline(Duration::from_secs(5)))
+        .exec(move |mut ctx| async move {
+            // Ignores `Terminate`.
+            while ctx.recv().await.is_some() {}
+            unreachable!();
+        });
+
+    let mut proxy = elfo::test::proxy(blueprint, elfo::config::AnyConfig::default()).await;
+    proxy.send(SubscribeToActorStatuses::default()).await;
+    proxy.sync().await;
+    while proxy.try_recv().await.is_some() {}
+
+    proxy.send(Terminate::default()).await;
+    tokio::time::sleep(Duration::from_secs(4)).await;
+    proxy.sync().await;
+    while let Some(envelope) = proxy.try_recv().await {
+        msg!(match envelope {
+            report @ ActorStatusReport => {
+                assert_ne!(report.status.kind(), ActorStatusKind::Aborted);
+            }
+        });
+    }
+
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    loop {
+        msg!(match proxy.recv().await {
+            report @ ActorStatusReport => {
+                if report.status.kind() == ActorStatusKind::Aborted {
+                    break;
+                }
+            }
+        });
+    }
+    proxy.finished().await;
+}
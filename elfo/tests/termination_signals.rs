@@ -0,0 +1,59 @@
+#![cfg(all(unix, feature = "test-util"))]
+// Signals are sent to the whole process, so this test has its own binary.
+
+use std::time::Duration;
+
+use elfo::{messages::Terminate, prelude::*, TerminationPolicy, Topology};
+
+mod common;
+
+#[tokio::test]
+async fn repeated_signals_escalate_termination() {
+    let (tx, rx) = common::channel();
+
+    let topology = Topology::empty();
+    let workers = topology.local("workers");
+
+    workers.mount(
+        ActorGroup::new()
+            .termination_policy(TerminationPolicy::manually())
+            .exec(move |mut ctx| {
+                let tx = tx.clone();
+
+                async move {
+                    tx.send("started").await.unwrap();
+
+                    // The polite `Terminate` is ignored.
+                    while let Some(envelope) = ctx.recv().await {
+                        msg!(match envelope {
+                            Terminate => tx.send("terminate").await.unwrap(),
+                            _ => {}
+                        });
+                    }
+
+                    tx.send("closed").await.unwrap();
+                }
+            }),
+    );
+
+    common::mount_configurers(&topology);
+    let node = tokio::spawn(elfo::init::start(topology));
+
+    // Signals are listened before groups are started, so it cannot be lost.
+    assert_eq!(rx.receive().await, Some("started"));
+    send_terminate();
+    assert_eq!(rx.receive().await, Some("terminate"));
+
+    // The second signal closes mailboxes without waiting for the usual delay.
+    send_terminate();
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.receive()).await;
+    assert_eq!(event.unwrap(), Some("closed"));
+
+    node.await.unwrap();
+}
+
+fn send_terminate() {
+    unsafe {
+        assert_eq!(libc::kill(libc::getpid(), libc::SIGTERM), 0);
+    }
+}